@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-declare_id!("Int5ecur22222222222222222222222222222222222");
+// SHARED UTILS/export.rs
+mod export;
+
+declare_id!("int5ecur22222222222222222222222222222222222");
 
 #[program]
 pub mod integer_overflow_secure {
@@ -65,6 +68,12 @@ pub mod integer_overflow_secure {
         msg!("  Average calculated safely: {}", user.tokens);
         Ok(())
     }
+
+    /// Snapshot of the users passed as remaining accounts, for auditors.
+    /// Writes nothing; simulate it and decode the return data
+    pub fn export_state(ctx: Context<ExportState>) -> Result<export::Snapshot> {
+        export::snapshot(ctx.program_id, ctx.remaining_accounts)
+    }
 }
 
 #[derive(Accounts)]
@@ -83,6 +92,9 @@ pub struct UpdateUser<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ExportState {}
+
 #[account]
 #[derive(InitSpace)]
 pub struct User {
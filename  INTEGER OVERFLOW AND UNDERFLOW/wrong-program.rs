@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-declare_id!("Int0ver11111111111111111111111111111111111");
+declare_id!("intover111111111111111111111111111111111111");
 
 #[program]
 pub mod integer_overflow_vulnerable {
@@ -1,7 +1,19 @@
 use anchor_lang::prelude::*;
 
+// SHARED UTILS/safe_cast.rs, used by percentage.rs
+mod safe_cast;
+
+// SHARED UTILS/percentage.rs
+mod percentage;
+
+// SHARED UTILS/export.rs
+mod export;
+
 declare_id!("Acc5ecur22222222222222222222222222222222222");
 
+/// Reward rate: 1% of points, in basis points
+pub const REWARD_BPS: u16 = 100;
+
 #[program]
 pub mod account_ownership_secure {
     use super::*;
@@ -23,7 +35,10 @@ pub mod account_ownership_secure {
         // - Owner is this program
         // - Data deserializes correctly
         // - Discriminator matches
-        user_account.points = user_account.points.checked_add(points).unwrap();
+        user_account.points = user_account
+            .points
+            .checked_add(points)
+            .ok_or(ErrorCode::Overflow)?;
         
         msg!(" Added {} points with ownership verification", points);
         Ok(())
@@ -34,11 +49,19 @@ pub mod account_ownership_secure {
         let user_account = &ctx.accounts.user_account;
         
         //  Safe to read - ownership verified
-        let reward = user_account.points / 100;
+        let reward = percentage::percentage_of(user_account.points, REWARD_BPS)?;
+        //  FIX: Fewer than 100 points floor to 0; refuse rather than "claim" nothing
+        require!(reward > 0, ErrorCode::RewardTooSmall);
         
         msg!(" Claiming {} tokens from verified account", reward);
         Ok(())
     }
+
+    /// Snapshot of the user accounts passed as remaining accounts, for
+    /// auditors. Writes nothing; simulate it and decode the return data
+    pub fn export_state(ctx: Context<ExportState>) -> Result<export::Snapshot> {
+        export::snapshot(ctx.program_id, ctx.remaining_accounts)
+    }
 }
 
 #[derive(Accounts)]
@@ -74,10 +97,21 @@ pub struct SecureClaimReward<'info> {
     pub owner: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ExportState {}
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserAccount {
     pub owner: Pubkey,
     pub balance: u64,
     pub points: u64,
-}
\ No newline at end of file
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Not enough points for a reward")]
+    RewardTooSmall,
+}
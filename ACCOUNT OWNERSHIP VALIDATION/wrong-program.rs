@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-declare_id!("Acc0wn111111111111111111111111111111111111");
+declare_id!("Accown1111111111111111111111111111111111111");
 
 #[program]
 pub mod account_ownership_vulnerable {
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+
+// SHARED UTILS/versioned.rs
+mod versioned;
+use versioned::{decode_body, read_versioned, version_of, write_versioned, VersionedAccount, HEADER_LEN};
+
+// SHARED UTILS/safe_cast.rs
+mod safe_cast;
+
+declare_id!("AcctVersion5ecur222222222222222222222222222");
+
+/// Parts per million: 1_000_000 is a 100% fee
+pub const PPM: u64 = 1_000_000;
+
+/// Release 1 stored basis points; one basis point is 100 ppm
+pub const PPM_PER_BPS: u32 = 100;
+
+/// `sha256("account:Config")[..8]`, the bytes `#[account]` would write
+pub const CONFIG_DISCRIMINATOR: [u8; 8] = [0x9b, 0x0c, 0xaa, 0xe0, 0x1e, 0xfa, 0xcc, 0x82];
+
+/// Release 2 of a payments program. The config has carried a version byte
+/// since release 1, which stored the fee in basis points; release 2 stores
+/// parts per million, for fees finer than 0.01%
+#[program]
+pub mod account_versioning_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, fee_ppm: u32) -> Result<()> {
+        require!(fee_ppm as u64 <= PPM, ErrorCode::InvalidFee);
+
+        let config = Config { admin: ctx.accounts.admin.key(), fee_ppm };
+        write_versioned(&ctx.accounts.config.to_account_info(), &config)?;
+        msg!("Fee set to {} ppm", fee_ppm);
+        Ok(())
+    }
+
+    /// Pays `amount` to `recipient`, less the fee, which stays in the config
+    ///
+    ///  SECURE: A release 1 config is decoded as release 1 and converted
+    pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+        //  FIX: The version byte picks the layout, not the program's release
+        let config: Config = read_versioned(&ctx.accounts.config, ctx.program_id)?;
+        let fee = fee_for(amount, config.fee_ppm)?;
+
+        transfer(&ctx.accounts, ctx.accounts.config.to_account_info(), fee)?;
+        transfer(&ctx.accounts, ctx.accounts.recipient.to_account_info(), amount - fee)?;
+
+        msg!(" Paid {} lamports, fee {}", amount - fee, fee);
+        Ok(())
+    }
+
+    /// Rewrites a config of any older version in the current layout, growing
+    /// the account if the new layout is larger
+    pub fn upgrade_config(ctx: Context<UpgradeConfig>) -> Result<()> {
+        let info = ctx.accounts.config.to_account_info();
+        let from = version_of::<Config>(&info.try_borrow_data()?)?;
+        let config: Config = read_versioned(&info, ctx.program_id)?;
+        require_keys_eq!(config.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+        let space = HEADER_LEN + Config::INIT_SPACE;
+        if info.data_len() < space {
+            let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: info.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_context, shortfall)?;
+            info.realloc(space, false)?;
+        }
+
+        write_versioned(&info, &config)?;
+        msg!(" Config upgraded from version {} to {}", from, Config::VERSION);
+        Ok(())
+    }
+}
+
+/// `fee_ppm <= PPM`, so the fee never exceeds `amount`
+fn fee_for(amount: u64, fee_ppm: u32) -> Result<u64> {
+    safe_cast::u128_to_u64(amount as u128 * fee_ppm as u128 / PPM as u128)
+}
+
+fn transfer<'info>(accounts: &Pay<'info>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: accounts.payer.to_account_info(),
+            to,
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, amount)
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// CHECK: Written by `write_versioned`
+    #[account(
+        init,
+        payer = admin,
+        space = HEADER_LEN + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    /// CHECK: Read by `read_versioned`, which checks the owner, the
+    /// discriminator and the version
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeConfig<'info> {
+    /// CHECK: Read by `read_versioned`; the admin is checked against it
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Version 1 body, as release 1 wrote it
+#[derive(AnchorDeserialize)]
+struct ConfigV1 {
+    admin: Pubkey,
+    fee_bps: u16,
+}
+
+/// Version 2 body, the one this release reads and writes
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    /// Fee in parts per million
+    pub fee_ppm: u32,
+}
+
+impl VersionedAccount for Config {
+    const DISCRIMINATOR: [u8; 8] = CONFIG_DISCRIMINATOR;
+    const VERSION: u8 = 2;
+
+    fn decode(version: u8, body: &mut &[u8]) -> Result<Self> {
+        match version {
+            1 => {
+                let v1: ConfigV1 = decode_body(body)?;
+                //  FIX: Converted to the current unit, not reinterpreted
+                let fee_ppm = u32::from(v1.fee_bps) * PPM_PER_BPS;
+                require!(fee_ppm as u64 <= PPM, ErrorCode::InvalidFee);
+                Ok(Config { admin: v1.admin, fee_ppm })
+            }
+            2 => decode_body(body),
+            _ => err!(versioned::VersionError::UnknownVersion),
+        }
+    }
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Fee must be at most 1,000,000 ppm")]
+    InvalidFee,
+    #[msg("Only the config's admin can upgrade it")]
+    Unauthorized,
+}
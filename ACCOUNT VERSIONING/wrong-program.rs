@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+declare_id!("AcctVersionvu1n1111111111111111111111111111");
+
+/// Parts per million: 1_000_000 is a 100% fee
+pub const PPM: u64 = 1_000_000;
+
+/// Spare bytes at the end of the config, left for fields added in later
+/// releases
+pub const RESERVED: usize = 64;
+
+/// Release 2 of a payments program. Release 1 stored the fee in basis
+/// points:
+///
+/// ```ignore
+/// #[account]
+/// pub struct Config {
+///     pub admin: Pubkey,
+///     pub fee_bps: u16,
+/// }
+/// ```
+///
+/// Release 2 switched to parts per million, for fees finer than 0.01%
+#[program]
+pub mod account_versioning_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, fee_ppm: u32) -> Result<()> {
+        require!(fee_ppm as u64 <= PPM, ErrorCode::InvalidFee);
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.fee_ppm = fee_ppm;
+        msg!("Fee set to {} ppm", fee_ppm);
+        Ok(())
+    }
+
+    /// Pays `amount` to `recipient`, less the fee, which stays in the config
+    ///
+    ///  VULNERABLE: Reads every config as release 2, including the ones
+    /// release 1 wrote
+    pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+        //  BUG: A release 1 config has the same discriminator and enough
+        // bytes, so its `fee_bps` and a reserved zero byte pair decode as
+        // `fee_ppm`: a 30 bps fee (0.3%) becomes 30 ppm (0.003%)
+        let fee = fee_for(amount, ctx.accounts.config.fee_ppm);
+
+        transfer(&ctx.accounts, ctx.accounts.config.to_account_info(), fee)?;
+        transfer(&ctx.accounts, ctx.accounts.recipient.to_account_info(), amount - fee)?;
+
+        msg!(" Paid {} lamports, fee {}", amount - fee, fee);
+        Ok(())
+    }
+}
+
+/// `fee_ppm <= PPM`, so the fee never exceeds `amount`
+fn fee_for(amount: u64, fee_ppm: u32) -> u64 {
+    (amount as u128 * fee_ppm as u128 / PPM as u128) as u64
+}
+
+fn transfer<'info>(accounts: &Pay<'info>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: accounts.payer.to_account_info(),
+            to,
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, amount)
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE + RESERVED,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    //  Same discriminator for both releases' layouts
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    /// Fee in parts per million. Was `fee_bps: u16` in release 1
+    pub fee_ppm: u32,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Fee must be at most 1,000,000 ppm")]
+    InvalidFee,
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+
+// SHARED UTILS/constant_product.rs
+mod constant_product;
+use constant_product::{CurveError, SwapQuote};
+
+declare_id!("Amm5ecur22222222222222222222222222222222222");
+
+#[program]
+pub mod amm_invariant_secure {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>, reserve_a: u64, reserve_b: u64) -> Result<()> {
+        require!(reserve_a > 0 && reserve_b > 0, ErrorCode::EmptyReserve);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.admin = ctx.accounts.admin.key();
+        pool.reserve_a = reserve_a;
+        pool.reserve_b = reserve_b;
+        pool.bump = ctx.bumps.pool;
+        msg!("Pool created with {} A / {} B", reserve_a, reserve_b);
+        Ok(())
+    }
+
+    pub fn open_trader(ctx: Context<OpenTrader>) -> Result<()> {
+        let trader = &mut ctx.accounts.trader;
+        trader.owner = ctx.accounts.owner.key();
+        trader.balance_a = 0;
+        trader.balance_b = 0;
+        trader.bump = ctx.bumps.trader;
+        Ok(())
+    }
+
+    /// Admin credits test balances (stands in for a token deposit)
+    pub fn fund_trader(ctx: Context<FundTrader>, amount_a: u64, amount_b: u64) -> Result<()> {
+        let trader = &mut ctx.accounts.trader;
+        trader.balance_a = trader.balance_a.checked_add(amount_a).ok_or(ErrorCode::Overflow)?;
+        trader.balance_b = trader.balance_b.checked_add(amount_b).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    ///  SECURE: Rounds in the pool's favor and enforces x * y >= k after every swap
+    pub fn swap(ctx: Context<Swap>, a_to_b: bool, amount_in: u64, min_out: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let trader = &mut ctx.accounts.trader;
+
+        let (reserve_in, reserve_out) = if a_to_b {
+            (pool.reserve_a, pool.reserve_b)
+        } else {
+            (pool.reserve_b, pool.reserve_a)
+        };
+
+        //  FIX: u128 products, floor division and `k_after >= k_before`,
+        // all in the pure curve math (SHARED UTILS/constant_product.rs)
+        let SwapQuote {
+            amount_out,
+            new_reserve_in: new_in,
+            new_reserve_out: new_out,
+        } = constant_product::quote_swap(reserve_in, reserve_out, amount_in).map_err(ErrorCode::from)?;
+
+        require!(amount_out >= min_out, ErrorCode::SlippageExceeded);
+
+        if a_to_b {
+            trader.balance_a = trader.balance_a.checked_sub(amount_in).ok_or(ErrorCode::InsufficientBalance)?;
+            trader.balance_b = trader.balance_b.checked_add(amount_out).ok_or(ErrorCode::Overflow)?;
+            pool.reserve_a = new_in;
+            pool.reserve_b = new_out;
+        } else {
+            trader.balance_b = trader.balance_b.checked_sub(amount_in).ok_or(ErrorCode::InsufficientBalance)?;
+            trader.balance_a = trader.balance_a.checked_add(amount_out).ok_or(ErrorCode::Overflow)?;
+            pool.reserve_b = new_in;
+            pool.reserve_a = new_out;
+        }
+
+        msg!(" Swapped {} in for {} out", amount_in, amount_out);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenTrader<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Trader::INIT_SPACE,
+        seeds = [b"trader", owner.key().as_ref()],
+        bump
+    )]
+    pub trader: Account<'info, Trader>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundTrader<'info> {
+    #[account(seeds = [b"pool"], bump = pool.bump, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, seeds = [b"trader", trader.owner.as_ref()], bump = trader.bump)]
+    pub trader: Account<'info, Trader>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"trader", owner.key().as_ref()],
+        bump = trader.bump,
+        has_one = owner,
+    )]
+    pub trader: Account<'info, Trader>,
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub admin: Pubkey,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Trader {
+    pub owner: Pubkey,
+    pub balance_a: u64,
+    pub balance_b: u64,
+    pub bump: u8,
+}
+
+impl From<CurveError> for ErrorCode {
+    fn from(err: CurveError) -> Self {
+        match err {
+            CurveError::Overflow => ErrorCode::Overflow,
+            CurveError::ZeroOutput => ErrorCode::ZeroOutput,
+            CurveError::EmptyReserve => ErrorCode::EmptyReserve,
+            CurveError::InvariantViolated => ErrorCode::InvariantViolated,
+        }
+    }
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient trader balance")]
+    InsufficientBalance,
+    #[msg("Output below minimum")]
+    SlippageExceeded,
+    #[msg("Swap would return nothing")]
+    ZeroOutput,
+    #[msg("Pool reserves must stay above zero")]
+    EmptyReserve,
+    #[msg("Swap would decrease reserve_a * reserve_b")]
+    InvariantViolated,
+}
@@ -1,7 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
 use anchor_spl::token::{self, Token, TokenAccount, Transfer as SplTransfer};
+use anchor_spl::token_interface::{
+    self, Mint as InterfaceMint, TokenAccount as InterfaceTokenAccount, TokenInterface,
+    TransferChecked,
+};
 
-declare_id!("CPI5ecur22222222222222222222222222222222222");
+declare_id!("Cpi5ecur22222222222222222222222222222222222");
 
 //  Whitelist of allowed programs
 pub const ALLOWED_PROGRAMS: &[Pubkey] = &[
@@ -34,6 +42,49 @@ pub mod arbitrary_cpi_secure {
         Ok(())
     }
 
+    pub fn open_ledger(ctx: Context<OpenLedger>) -> Result<()> {
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.owner = ctx.accounts.owner.key();
+        ledger.mint = ctx.accounts.mint.key();
+        ledger.credited = 0;
+        ledger.bump = ctx.bumps.ledger;
+        msg!("Ledger opened");
+        Ok(())
+    }
+
+    ///  SECURE: Credits what actually arrived, not what was requested
+    /// Token-2022 mints with a transfer fee withhold part of every transfer,
+    /// so `amount` overstates the deposit
+    pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
+        let before = ctx.accounts.vault_token.amount;
+
+        //  token_program is validated by Interface<'info, TokenInterface>:
+        // Token or Token-2022, nothing else
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.from.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault_token.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+        //  FIX: Re-read the vault after the CPI and credit the difference
+        ctx.accounts.vault_token.reload()?;
+        let received = ctx
+            .accounts
+            .vault_token
+            .amount
+            .checked_sub(before)
+            .ok_or(ErrorCode::Overflow)?;
+
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.credited = ledger.credited.checked_add(received).ok_or(ErrorCode::Overflow)?;
+
+        msg!(" Sent {} tokens, credited {} after transfer fees", amount, received);
+        Ok(())
+    }
+
     ///  SECURE: Validates program against whitelist
     pub fn call_whitelisted_program(
         ctx: Context<CallWhitelisted>,
@@ -48,6 +99,39 @@ pub mod arbitrary_cpi_secure {
         Ok(())
     }
 
+    ///  SECURE: Forwards a call only to a program on the whitelist
+    /// Same shape as the vulnerable `call_external`: `data` and the
+    /// remaining accounts go to `program_id` with their flags unchanged
+    pub fn call_external(
+        ctx: Context<CallExternal>,
+        program_id: Pubkey,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        //  FIX: Check the id before building anything from it
+        require!(
+            ALLOWED_PROGRAMS.contains(&program_id),
+            ErrorCode::UnauthorizedProgram
+        );
+
+        let ix = Instruction {
+            program_id,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        invoke(&ix, ctx.remaining_accounts)?;
+
+        msg!(" Called whitelisted program: {}", program_id);
+        Ok(())
+    }
+
     ///  SECURE: Uses System program (known program)
     pub fn transfer_sol(
         ctx: Context<TransferSol>,
@@ -81,6 +165,53 @@ pub struct SecureTokenTransfer<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct OpenLedger<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Ledger::INIT_SPACE,
+        seeds = [b"ledger", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub ledger: Account<'info, Ledger>,
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+///  SECURE: Works with Token and Token-2022, vault owned by this program
+#[derive(Accounts)]
+pub struct DepositTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"ledger", authority.key().as_ref(), mint.key().as_ref()],
+        bump = ledger.bump,
+    )]
+    pub ledger: Account<'info, Ledger>,
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub from: InterfaceAccount<'info, InterfaceTokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault_authority,
+        token::token_program = token_program,
+    )]
+    pub vault_token: InterfaceAccount<'info, InterfaceTokenAccount>,
+    /// CHECK: PDA that owns `vault_token`; only its address is used here
+    #[account(seeds = [b"vault", mint.key().as_ref()], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 ///  SECURE: Validates against whitelist
 #[derive(Accounts)]
 pub struct CallWhitelisted<'info> {
@@ -89,6 +220,13 @@ pub struct CallWhitelisted<'info> {
     pub target_program: AccountInfo<'info>,
 }
 
+///  SECURE: The target is checked against the whitelist in the
+/// instruction, before the CPI is built
+#[derive(Accounts)]
+pub struct CallExternal<'info> {
+    pub authority: Signer<'info>,
+}
+
 ///  SECURE: Uses Program<'info, System>
 #[derive(Accounts)]
 pub struct TransferSol<'info> {
@@ -100,8 +238,21 @@ pub struct TransferSol<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Tokens credited to `owner` for one mint
+#[account]
+#[derive(InitSpace)]
+pub struct Ledger {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// Sum of amounts that actually reached the vault
+    pub credited: u64,
+    pub bump: u8,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Unauthorized program")]
     UnauthorizedProgram,
+    #[msg("Arithmetic overflow")]
+    Overflow,
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+declare_id!("FakeSystem111111111111111111111111111111111");
+
+/// Stand-in for the System Program, used by the raw CPI tests. Accepts
+/// the same `Transfer` instruction data and reports success without
+/// moving a single lamport
+#[program]
+pub mod fake_system_program {
+    use super::*;
+
+    /// Anchor routes instruction data with no matching discriminator here,
+    /// so the System Program layout (u32 index + u64 lamports) lands in this
+    /// function instead of failing to deserialize
+    pub fn fallback<'info>(
+        _program_id: &Pubkey,
+        _accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        let lamports = data
+            .get(4..12)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .unwrap_or_default();
+
+        //  The caller sees Ok(()) - exactly what a real transfer returns
+        msg!("FakeSystem: reported a {} lamport transfer, moved nothing", lamports);
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke,
+    entrypoint,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_pubkey::pubkey;
+
+pinocchio_pubkey::declare_id!("PinoCpi5ecur2222222222222222222222222222222");
+
+/// Same value as `spl_token::ID` - Pinocchio has no `Program<'info, Token>`
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Token `Transfer` instruction tag
+const TRANSFER_DISCRIMINATOR: u8 = 3;
+
+entrypoint!(process_instruction);
+
+///  SECURE: Only ever invokes the real Token program
+///
+/// Accounts:
+///   0. `[writable]` source token account
+///   1. `[writable]` destination token account
+///   2. `[signer]`   source authority
+///   3. `[]`         token program - must be `TOKEN_PROGRAM_ID`
+///
+/// Data: `amount: u64` (little-endian)
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [source, destination, authority, token_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let amount = u64::from_le_bytes(
+        instruction_data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    //  FIX: The check Anchor's Program<'info, Token> does for you
+    if token_program.key() != &TOKEN_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut data = [0u8; 9];
+    data[0] = TRANSFER_DISCRIMINATOR;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    let account_metas = [
+        AccountMeta::writable(source.key()),
+        AccountMeta::writable(destination.key()),
+        AccountMeta::readonly_signer(authority.key()),
+    ];
+
+    //  FIX: program_id is the constant, not the account's key
+    let instruction = Instruction {
+        program_id: &TOKEN_PROGRAM_ID,
+        accounts: &account_metas,
+        data: &data,
+    };
+
+    invoke(&instruction, &[source, destination, authority])?;
+
+    msg!(" Executed CPI to validated Token program");
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke,
+    entrypoint,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pinocchio_pubkey::declare_id!("PinoCpivu1n11111111111111111111111111111111");
+
+/// SPL Token `Transfer` instruction tag
+const TRANSFER_DISCRIMINATOR: u8 = 3;
+
+entrypoint!(process_instruction);
+
+///  VULNERABLE: Forwards the authority's signature to ANY program!
+///
+/// Accounts:
+///   0. `[writable]` source token account
+///   1. `[writable]` destination token account
+///   2. `[signer]`   source authority
+///   3. `[]`         token program - never checked
+///
+/// Data: `amount: u64` (little-endian)
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [source, destination, authority, token_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let amount = u64::from_le_bytes(
+        instruction_data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut data = [0u8; 9];
+    data[0] = TRANSFER_DISCRIMINATOR;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    let account_metas = [
+        AccountMeta::writable(source.key()),
+        AccountMeta::writable(destination.key()),
+        AccountMeta::readonly_signer(authority.key()),
+    ];
+
+    //  program_id comes straight from the caller-supplied account
+    // A malicious program receives the authority's signature and can
+    // use it for anything: approve, set_authority, drain other accounts
+    let instruction = Instruction {
+        program_id: token_program.key(),
+        accounts: &account_metas,
+        data: &data,
+    };
+
+    invoke(&instruction, &[source, destination, authority])?;
+
+    msg!(" Executed CPI to unvalidated program!");
+    Ok(())
+}
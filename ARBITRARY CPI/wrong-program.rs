@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke, instruction::Instruction, instruction::AccountMeta};
 
-declare_id!("CPIvu1n11111111111111111111111111111111111");
+declare_id!("Cpivu1n111111111111111111111111111111111111");
 
 #[program]
 pub mod arbitrary_cpi_vulnerable {
@@ -21,7 +21,7 @@ pub mod arbitrary_cpi_vulnerable {
                 AccountMeta::new(ctx.accounts.from.key(), true),
                 AccountMeta::new(ctx.accounts.to.key(), false),
             ],
-            data: amount.to_le_bytes().to_vec(),
+            data: system_transfer_data(amount),
         };
         
         //  Calling unvalidated program!
@@ -38,19 +38,46 @@ pub mod arbitrary_cpi_vulnerable {
         Ok(())
     }
 
-    ///  VULNERABLE: Accepts any program_id parameter
+    ///  VULNERABLE: Invokes whatever program the caller names
+    /// Forwards `data` and the remaining accounts, with the flags they came
+    /// in with, to `program_id`. The callee's own account goes among the
+    /// remaining accounts so the runtime can load it
     pub fn call_external(
         ctx: Context<CallExternal>,
         program_id: Pubkey,
+        data: Vec<u8>,
     ) -> Result<()> {
-        //  Using program_id from user input!
-        msg!(" Calling program: {}", program_id);
-        
-        // Malicious CPI would happen here
+        //  program_id comes straight from instruction data!
+        let ix = Instruction {
+            program_id,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+
+        //  Any signer in the list is now the callee's to spend
+        invoke(&ix, ctx.remaining_accounts)?;
+
+        msg!(" Called program: {}", program_id);
         Ok(())
     }
 }
 
+/// Instruction data in the System Program's `Transfer` layout: a u32
+/// instruction index (2) followed by the lamports, both little-endian
+fn system_transfer_data(amount: u64) -> Vec<u8> {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
 ///  VULNERABLE: No validation on target_program
 #[derive(Accounts)]
 pub struct VulnerableTransfer<'info> {
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+declare_id!("AuthCon5ecur2222222222222222222222222222222");
+
+#[program]
+pub mod authority_consistency_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: The authority never changes in place - the vault moves to
+    /// the new authority's PDA, so seeds and `vault.authority` always agree
+    pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
+        let balance = ctx.accounts.vault.balance;
+
+        let new_vault = &mut ctx.accounts.new_vault;
+        new_vault.authority = new_authority;
+        new_vault.balance = balance;
+        new_vault.bump = ctx.bumps.new_vault;
+
+        //  FIX: Deposits follow the vault; `close = authority` refunds the old rent
+        ctx.accounts.vault.sub_lamports(balance)?;
+        ctx.accounts.new_vault.add_lamports(balance)?;
+
+        msg!("Vault moved to authority {}", new_authority);
+        Ok(())
+    }
+
+    ///  SECURE: Seeds and has_one both name the same key
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_authority: Pubkey)]
+pub struct TransferAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+        close = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    ///  FIX: Re-created at the address the new authority derives
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", new_authority.as_ref()],
+        bump
+    )]
+    pub new_vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    ///  FIX: Both checks, and they can no longer disagree
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+declare_id!("AuthConvu1n11111111111111111111111111111111");
+
+#[program]
+pub mod authority_consistency_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Updates the stored authority, but the PDA address
+    /// is still derived from the old one!
+    pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.vault.authority = new_authority;
+        msg!("Authority transferred to {}", new_authority);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Authorized by the seeds alone, which still name the old authority
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    /// Checked against the stored authority
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    ///  BUG: Checked against the seeds - the original authority, forever
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+declare_id!("AuthSetvu1n11111111111111111111111111111111");
+
+pub const MAX_AUTHORITIES: usize = 8;
+
+#[program]
+pub mod authority_set_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.admin = ctx.accounts.admin.key();
+        treasury.authorities = Vec::new();
+        treasury.balance = 0;
+        treasury.bump = ctx.bumps.treasury;
+        msg!("Treasury initialized, admin: {}", treasury.admin);
+        Ok(())
+    }
+
+    pub fn add_authority(ctx: Context<ManageAuthorities>, authority: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        require!(
+            treasury.authorities.len() < MAX_AUTHORITIES,
+            ErrorCode::TooManyAuthorities
+        );
+        require!(
+            !treasury.authorities.contains(&authority),
+            ErrorCode::DuplicateAuthority
+        );
+        treasury.authorities.push(authority);
+        msg!("Authority added: {}", authority);
+        Ok(())
+    }
+
+    pub fn remove_authority(ctx: Context<ManageAuthorities>, authority: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let index = treasury
+            .authorities
+            .iter()
+            .position(|key| *key == authority)
+            .ok_or(ErrorCode::NotAnAuthority)?;
+        treasury.authorities.swap_remove(index);
+        msg!("Authority removed: {}", authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.balance = treasury.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Checks the key is in the set, never that it signed!
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        //  A public list of public keys - anyone can pass one of them
+        let mut is_authority = false;
+        for key in treasury.authorities.iter() {
+            if key == ctx.accounts.authority.key {
+                is_authority = true;
+                break;
+            }
+        }
+        require!(is_authority, ErrorCode::NotAnAuthority);
+
+        treasury.balance = treasury.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        treasury.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAuthorities<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump, has_one = admin)]
+    pub treasury: Account<'info, Treasury>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    ///  BUG: Should be Signer<'info>
+    /// CHECK: Only compared against treasury.authorities
+    pub authority: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub admin: Pubkey,
+    #[max_len(MAX_AUTHORITIES)]
+    pub authorities: Vec<Pubkey>,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in treasury")]
+    InsufficientFunds,
+    #[msg("Authority set is full")]
+    TooManyAuthorities,
+    #[msg("Authority is already in the set")]
+    DuplicateAuthority,
+    #[msg("Not an authority of this treasury")]
+    NotAnAuthority,
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+declare_id!("Freeze5ecur22222222222222222222222222222222");
+
+/// Length of the velocity window (1 hour)
+pub const WINDOW_SECS: i64 = 60 * 60;
+
+/// Most that may leave the vault within one window
+pub const MAX_WITHDRAWN_PER_WINDOW: u64 = 5 * LAMPORTS_PER_SOL;
+
+#[program]
+pub mod auto_freeze_secure {
+    use super::*;
+
+    /// Authority owns the funds; `admin` is a separate guardian key
+    pub fn initialize(ctx: Context<Initialize>, admin: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.admin = admin;
+        vault.balance = 0;
+        vault.window_start_ts = Clock::get()?.unix_timestamp;
+        vault.withdrawn_in_window = 0;
+        vault.frozen = false;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Trips a circuit breaker when too much leaves too fast
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        require!(!vault.frozen, ErrorCode::VaultFrozen);
+
+        //  FIX: Start a fresh window once the old one has elapsed
+        let now = Clock::get()?.unix_timestamp;
+        if now - vault.window_start_ts >= WINDOW_SECS {
+            vault.window_start_ts = now;
+            vault.withdrawn_in_window = 0;
+        }
+
+        let withdrawn = vault
+            .withdrawn_in_window
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        //  FIX: Over the limit - freeze and move nothing. Returning an error
+        // here would roll back `frozen` along with everything else
+        if withdrawn > MAX_WITHDRAWN_PER_WINDOW {
+            vault.frozen = true;
+            msg!("Withdrawal velocity exceeded, vault frozen");
+            return Ok(());
+        }
+
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.withdrawn_in_window = withdrawn;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    /// Only the admin lifts a freeze; the window starts over
+    pub fn unfreeze(ctx: Context<Unfreeze>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.frozen = false;
+        vault.window_start_ts = Clock::get()?.unix_timestamp;
+        vault.withdrawn_in_window = 0;
+        msg!("Vault unfrozen by admin");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unfreeze<'info> {
+    //  FIX: The authority cannot unfreeze its own vault
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+        has_one = admin,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub admin: Pubkey,
+    pub balance: u64,
+    ///  FIX: Velocity tracking for the current window
+    pub window_start_ts: i64,
+    pub withdrawn_in_window: u64,
+    pub frozen: bool,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Vault is frozen until the admin unfreezes it")]
+    VaultFrozen,
+}
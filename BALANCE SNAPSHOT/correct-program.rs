@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+declare_id!("Snap5ecur2222222222222222222222222222222222");
+
+#[program]
+pub mod balance_snapshot_secure {
+    use super::*;
+
+    /// Admin sets up the vault and names the strategy it harvests from
+    pub fn initialize(ctx: Context<Initialize>, strategy_program: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.strategy_program = strategy_program;
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for strategy {}", strategy_program);
+        Ok(())
+    }
+
+    ///  SECURE: Credits `amount` only if exactly `amount` arrived
+    pub fn harvest(ctx: Context<Harvest>, amount: u64) -> Result<()> {
+        //  FIX: Snapshot the account the CPI is supposed to change
+        let before = ctx.accounts.vault.to_account_info().lamports();
+
+        // Anchor instruction data: sha256("global:pay_out")[..8] ++ amount
+        let mut data = hash(b"global:pay_out").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let ix = Instruction {
+            program_id: ctx.accounts.strategy_program.key(),
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.reserve.key(), false),
+                AccountMeta::new(ctx.accounts.vault.key(), false),
+            ],
+            data,
+        };
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.reserve.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+            ],
+        )?;
+
+        //  FIX: `?` only says the callee didn't fail - the delta says what it did
+        let after = ctx.accounts.vault.to_account_info().lamports();
+        let received = after.checked_sub(before).ok_or(ErrorCode::UnexpectedBalanceChange)?;
+        require!(received == amount, ErrorCode::UnexpectedBalanceChange);
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        msg!(" Harvested {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.admin.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    #[account(mut, seeds = [b"vault"], bump = vault.bump, has_one = admin)]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: The strategy's reserve; the strategy validates its own account
+    #[account(mut, owner = vault.strategy_program)]
+    pub reserve: AccountInfo<'info>,
+    /// CHECK: Pinned to the configured strategy - this is not an arbitrary CPI
+    #[account(address = vault.strategy_program, executable)]
+    pub strategy_program: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"vault"], bump = vault.bump, has_one = admin)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub admin: Pubkey,
+    pub strategy_program: Pubkey,
+    /// Lamports this vault believes it holds on top of rent
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Strategy did not move exactly the expected amount")]
+    UnexpectedBalanceChange,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+// SHARED UTILS/safe_cast.rs
+mod safe_cast;
+
+declare_id!("SnapStrategy1111111111111111111111111111111");
+
+/// The yield strategy both vault programs harvest from. Used by the tests
+/// as the callee: honest while `skim_bps` is 0, short-paying once it isn't -
+/// standing in for a buggy upgrade, an undisclosed fee or a compromised admin
+#[program]
+pub mod snap_strategy {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let reserve = &mut ctx.accounts.reserve;
+        reserve.admin = ctx.accounts.admin.key();
+        reserve.skim_bps = 0;
+        reserve.bump = ctx.bumps.reserve;
+        msg!("Strategy reserve initialized");
+        Ok(())
+    }
+
+    pub fn fund(ctx: Context<Fund>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.reserve.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        msg!("Reserve funded with {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn set_skim(ctx: Context<SetSkim>, skim_bps: u16) -> Result<()> {
+        ctx.accounts.reserve.skim_bps = skim_bps;
+        msg!("Skim set to {} bps", skim_bps);
+        Ok(())
+    }
+
+    /// Pays `amount` out of the reserve to `recipient` - minus the skim
+    pub fn pay_out(ctx: Context<PayOut>, amount: u64) -> Result<()> {
+        let skim = safe_cast::u128_to_u64(amount as u128 * ctx.accounts.reserve.skim_bps as u128 / 10_000)?;
+        let paid = amount - skim;
+
+        ctx.accounts.reserve.sub_lamports(paid)?;
+        ctx.accounts.recipient.add_lamports(paid)?;
+
+        //  Returns Ok either way: the caller's `?` sees nothing wrong
+        msg!("Paid out {} of {} lamports", paid, amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Reserve::INIT_SPACE,
+        seeds = [b"reserve"],
+        bump
+    )]
+    pub reserve: Account<'info, Reserve>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Fund<'info> {
+    #[account(mut, seeds = [b"reserve"], bump = reserve.bump)]
+    pub reserve: Account<'info, Reserve>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSkim<'info> {
+    #[account(mut, seeds = [b"reserve"], bump = reserve.bump, has_one = admin)]
+    pub reserve: Account<'info, Reserve>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PayOut<'info> {
+    #[account(mut, seeds = [b"reserve"], bump = reserve.bump)]
+    pub reserve: Account<'info, Reserve>,
+    /// CHECK: Any account can be paid
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Reserve {
+    pub admin: Pubkey,
+    pub skim_bps: u16,
+    pub bump: u8,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    sysvar::instructions,
+};
+
+declare_id!("ArcadeCa11er1111111111111111111111111111111");
+
+/// Most points one round can award
+pub const MAX_ROUND_POINTS: u64 = 100;
+
+/// The game program the ledger's admin trusts. Used by the tests against
+/// both ledger programs: it applies the game's rules, then awards the
+/// points through CPI
+#[program]
+pub mod arcade {
+    use super::*;
+
+    pub fn finish_round(ctx: Context<FinishRound>, score: u64) -> Result<()> {
+        //  The rule the ledger relies on the game to enforce
+        require!(score <= MAX_ROUND_POINTS, ArcadeError::ScoreTooHigh);
+
+        // Anchor instruction data: sha256("global:award_points")[..8] ++ amount
+        let mut data = hash(b"global:award_points").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&score.to_le_bytes());
+
+        let ix = Instruction {
+            program_id: ctx.accounts.ledger_program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(ctx.accounts.config.key(), false),
+                AccountMeta::new(ctx.accounts.points.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.player.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.instructions_sysvar.key(), false),
+            ],
+            data,
+        };
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.config.to_account_info(),
+                ctx.accounts.points.to_account_info(),
+                ctx.accounts.player.to_account_info(),
+                ctx.accounts.instructions_sysvar.to_account_info(),
+            ],
+        )?;
+
+        msg!("Round finished: {} points", score);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct FinishRound<'info> {
+    /// CHECK: The ledger's config; the ledger validates it
+    pub config: AccountInfo<'info>,
+    /// CHECK: The player's points account; the ledger validates it
+    #[account(mut)]
+    pub points: AccountInfo<'info>,
+    pub player: Signer<'info>,
+    /// CHECK: Address constraint pins it to the sysvar
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: Vulnerable or secure ledger program
+    pub ledger_program: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum ArcadeError {
+    #[msg("Score is above the per-round maximum")]
+    ScoreTooHigh,
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{
+    self, load_current_index_checked, load_instruction_at_checked,
+};
+
+declare_id!("Ca11er5ecur22222222222222222222222222222222");
+
+/// Most caller programs the allowlist can hold
+pub const MAX_CALLERS: usize = 4;
+
+#[program]
+pub mod caller_auth_secure {
+    use super::*;
+
+    /// Admin sets up the ledger with the programs allowed to award points
+    pub fn initialize(ctx: Context<Initialize>, allowed_callers: Vec<Pubkey>) -> Result<()> {
+        require!(allowed_callers.len() <= MAX_CALLERS, ErrorCode::TooManyCallers);
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.allowed_callers = allowed_callers;
+        config.bump = ctx.bumps.config;
+        msg!("Ledger initialized with {} allowed callers", config.allowed_callers.len());
+        Ok(())
+    }
+
+    /// Admin replaces the allowlist, e.g. to retire an old game version
+    pub fn set_allowed_callers(
+        ctx: Context<SetAllowedCallers>,
+        allowed_callers: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(allowed_callers.len() <= MAX_CALLERS, ErrorCode::TooManyCallers);
+
+        ctx.accounts.config.allowed_callers = allowed_callers;
+        msg!(" Allowlist updated: {} callers", ctx.accounts.config.allowed_callers.len());
+        Ok(())
+    }
+
+    /// Player opens their points account
+    pub fn register(ctx: Context<Register>) -> Result<()> {
+        let points = &mut ctx.accounts.points;
+        points.player = ctx.accounts.player.key();
+        points.balance = 0;
+        points.bump = ctx.bumps.points;
+        msg!(" Registered {}", points.player);
+        Ok(())
+    }
+
+    ///  SECURE: Only a CPI straight from an allowlisted program gets through
+    pub fn award_points(ctx: Context<AwardPoints>, amount: u64) -> Result<()> {
+        //  FIX: Find out which program is calling, then check it
+        let caller = calling_program(&ctx.accounts.instructions_sysvar)?;
+        require!(
+            ctx.accounts.config.allowed_callers.contains(&caller),
+            ErrorCode::UnauthorizedCaller
+        );
+
+        let points = &mut ctx.accounts.points;
+        points.balance = points
+            .balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!(" Awarded {} points via {}, balance {}", amount, caller, points.balance);
+        Ok(())
+    }
+}
+
+/// The program that invoked this one. The instructions sysvar only lists
+/// top-level instructions, so the current one's `program_id` is our caller
+/// only when we run exactly one CPI deep. A direct call has no caller, and
+/// deeper down the top-level program may not be the one that invoked us -
+/// both are refused rather than guessed at
+fn calling_program(instructions_sysvar: &AccountInfo) -> Result<Pubkey> {
+    let height = get_stack_height();
+    require!(
+        height != TRANSACTION_LEVEL_STACK_HEIGHT,
+        ErrorCode::DirectCallNotAllowed
+    );
+    require!(
+        height == TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+        ErrorCode::NestedCallNotAllowed
+    );
+
+    let current = load_current_index_checked(instructions_sysvar)?;
+    let top_level = load_instruction_at_checked(current as usize, instructions_sysvar)?;
+    Ok(top_level.program_id)
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedCallers<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Register<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Points::INIT_SPACE,
+        seeds = [b"points", player.key().as_ref()],
+        bump
+    )]
+    pub points: Account<'info, Points>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AwardPoints<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"points", player.key().as_ref()],
+        bump = points.bump,
+    )]
+    pub points: Account<'info, Points>,
+    pub player: Signer<'info>,
+    ///  FIX: The real instructions sysvar, not a look-alike account
+    /// CHECK: Address constraint pins it to the sysvar
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    #[max_len(MAX_CALLERS)]
+    pub allowed_callers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Points {
+    pub player: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Allowlist holds at most 4 caller programs")]
+    TooManyCallers,
+    #[msg("award_points must be called by a program through CPI")]
+    DirectCallNotAllowed,
+    #[msg("award_points must be called directly by the caller program")]
+    NestedCallNotAllowed,
+    #[msg("Calling program is not on the allowlist")]
+    UnauthorizedCaller,
+}
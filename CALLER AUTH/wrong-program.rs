@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Ca11ervu1n111111111111111111111111111111111");
+
+#[program]
+pub mod caller_auth_vulnerable {
+    use super::*;
+
+    /// Admin sets up the ledger and names the game program allowed to award points
+    pub fn initialize(ctx: Context<Initialize>, game_program: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.game_program = game_program;
+        config.bump = ctx.bumps.config;
+        msg!("Ledger initialized for game {}", game_program);
+        Ok(())
+    }
+
+    /// Player opens their points account
+    pub fn register(ctx: Context<Register>) -> Result<()> {
+        let points = &mut ctx.accounts.points;
+        points.player = ctx.accounts.player.key();
+        points.balance = 0;
+        points.bump = ctx.bumps.points;
+        msg!(" Registered {}", points.player);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Meant to be called only by the game program through CPI,
+    /// after it has checked the round. Nothing here knows who called it
+    pub fn award_points(ctx: Context<AwardPoints>, amount: u64) -> Result<()> {
+        //  BUG: config.game_program is stored but never compared to anything.
+        // A direct call, or a CPI from any program, awards whatever it asks for
+        let points = &mut ctx.accounts.points;
+        points.balance = points
+            .balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!(" Awarded {} points, balance {}", amount, points.balance);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Register<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Points::INIT_SPACE,
+        seeds = [b"points", player.key().as_ref()],
+        bump
+    )]
+    pub points: Account<'info, Points>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AwardPoints<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"points", player.key().as_ref()],
+        bump = points.bump,
+    )]
+    pub points: Account<'info, Points>,
+    pub player: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub game_program: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Points {
+    pub player: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
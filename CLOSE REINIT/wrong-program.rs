@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Reinitvu1n111111111111111111111111111111111");
+
+#[program]
+pub mod close_reinit_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.admin = ctx.accounts.admin.key();
+        pool.bump = ctx.bumps.pool;
+        msg!("Reward pool initialized");
+        Ok(())
+    }
+
+    pub fn fund(ctx: Context<Fund>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        msg!("Pool funded with {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn issue_ticket(ctx: Context<IssueTicket>, ticket_id: u64, amount: u64) -> Result<()> {
+        let ticket = &mut ctx.accounts.ticket;
+        ticket.owner = ctx.accounts.owner.key();
+        ticket.ticket_id = ticket_id;
+        ticket.amount = amount;
+        ticket.bump = ctx.bumps.ticket;
+        msg!("Ticket {} issued for {} lamports", ticket_id, amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Pays the ticket, then "closes" it by draining lamports only!
+    /// Data and owner are untouched, so refunding the rent in the same
+    /// transaction keeps the ticket alive for another redeem
+    pub fn redeem(ctx: Context<Redeem>) -> Result<()> {
+        let amount = ctx.accounts.ticket.amount;
+
+        ctx.accounts.pool.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        //  Manual close: lamports out, nothing else
+        // The runtime only deletes zero-lamport accounts at the END of the
+        // transaction - a later instruction can top the ticket back up
+        let ticket_info = ctx.accounts.ticket.to_account_info();
+        let rent = ticket_info.lamports();
+        ticket_info.sub_lamports(rent)?;
+        ctx.accounts.owner.add_lamports(rent)?;
+
+        msg!(" Redeemed ticket {} for {} lamports", ctx.accounts.ticket.ticket_id, amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Fund<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket_id: u64)]
+pub struct IssueTicket<'info> {
+    #[account(seeds = [b"pool"], bump = pool.bump, has_one = admin)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Ticket::INIT_SPACE,
+        seeds = [b"ticket", owner.key().as_ref(), &ticket_id.to_le_bytes()],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    /// CHECK: Recipient of the ticket
+    pub owner: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    ///  No `close` constraint - closing is done by hand above
+    #[account(
+        mut,
+        seeds = [b"ticket", owner.key().as_ref(), &ticket.ticket_id.to_le_bytes()],
+        bump = ticket.bump,
+        has_one = owner,
+    )]
+    pub ticket: Account<'info, Ticket>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub admin: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Ticket {
+    pub owner: Pubkey,
+    pub ticket_id: u64,
+    pub amount: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+
+declare_id!("CPendvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod close_with_pending_vulnerable {
+    use super::*;
+
+    /// The owner opens a vault; a sponsor pays its rent and gets it back on close
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.rent_payer = ctx.accounts.rent_payer.key();
+        vault.balance = 0;
+        vault.pending = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for owner: {}", vault.owner);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    /// Sets `amount` aside for `recipient`, who claims it later. The
+    /// lamports stay in the vault until then
+    pub fn schedule_payment(ctx: Context<SchedulePayment>, payment_id: u64, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.pending = vault.pending.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        let payment = &mut ctx.accounts.payment;
+        payment.vault = vault.key();
+        payment.recipient = ctx.accounts.recipient.key();
+        payment.payment_id = payment_id;
+        payment.amount = amount;
+        payment.bump = ctx.bumps.payment;
+        msg!("Payment {} of {} lamports scheduled for {}", payment_id, amount, payment.recipient);
+        Ok(())
+    }
+
+    pub fn claim_payment(ctx: Context<ClaimPayment>) -> Result<()> {
+        let amount = ctx.accounts.payment.amount;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.pending = vault.pending.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        msg!(" Claimed payment {} for {} lamports", ctx.accounts.payment.payment_id, amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Closes the vault whatever it still holds or owes!
+    pub fn close_vault(_ctx: Context<CloseVault>) -> Result<()> {
+        //  BUG: No check on `balance` or `pending`. `close = rent_payer`
+        // sends every lamport in the vault to the rent payer - the owner's
+        // balance and the lamports set aside for recipients included - and
+        // leaves each Payment pointing at a vault that no longer exists
+        msg!(" Vault closed");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = rent_payer,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"vault", vault.owner.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(payment_id: u64)]
+pub struct SchedulePayment<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Payment::INIT_SPACE,
+        seeds = [b"payment", vault.key().as_ref(), &payment_id.to_le_bytes()],
+        bump
+    )]
+    pub payment: Account<'info, Payment>,
+    /// CHECK: Whoever the owner is paying
+    pub recipient: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPayment<'info> {
+    #[account(mut, seeds = [b"vault", vault.owner.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    //  The owner paid this account's rent in `schedule_payment`, so the
+    // owner gets it back; the recipient gets exactly `amount`
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"payment", vault.key().as_ref(), &payment.payment_id.to_le_bytes()],
+        bump = payment.bump,
+        has_one = vault,
+        has_one = recipient,
+    )]
+    pub payment: Account<'info, Payment>,
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    #[account(mut, address = vault.owner)]
+    pub owner: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+        has_one = rent_payer,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+    /// CHECK: Checked against `vault.rent_payer`; receives the rent back
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub rent_payer: Pubkey,
+    /// Lamports the owner can withdraw
+    pub balance: u64,
+    /// Lamports set aside for scheduled payments, not yet claimed
+    pub pending: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Payment {
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub payment_id: u64,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
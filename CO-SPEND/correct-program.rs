@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+declare_id!("CoSpend5ecur2222222222222222222222222222222");
+
+#[program]
+pub mod co_spend_secure {
+    use super::*;
+
+    ///  SECURE: Also refuses a "joint" account with one party listed twice
+    pub fn open(ctx: Context<Open>) -> Result<()> {
+        require_keys_neq!(
+            ctx.accounts.party_a.key(),
+            ctx.accounts.party_b.key(),
+            ErrorCode::DuplicateParty
+        );
+
+        let joint = &mut ctx.accounts.joint;
+        joint.party_a = ctx.accounts.party_a.key();
+        joint.party_b = ctx.accounts.party_b.key();
+        joint.bump = ctx.bumps.joint;
+        msg!("Joint account opened");
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.joint.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Marks off each required party by key, then requires all of them
+    pub fn co_spend(ctx: Context<CoSpend>, amount: u64) -> Result<()> {
+        let joint = &ctx.accounts.joint;
+        let required = [joint.party_a, joint.party_b];
+
+        //  FIX: One flag per required party - a key seen twice sets one flag
+        let mut consented = [false; 2];
+        for signer in [&ctx.accounts.first_signer, &ctx.accounts.second_signer] {
+            let index = required
+                .iter()
+                .position(|party| *party == signer.key())
+                .ok_or(ErrorCode::NotAParty)?;
+            consented[index] = true;
+        }
+        require!(consented.iter().all(|c| *c), ErrorCode::MissingConsent);
+
+        ctx.accounts.joint.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Co-spent {} lamports with both parties' consent", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Open<'info> {
+    #[account(
+        init,
+        payer = party_a,
+        space = 8 + Joint::INIT_SPACE,
+        seeds = [b"joint", party_a.key().as_ref(), party_b.key().as_ref()],
+        bump
+    )]
+    pub joint: Account<'info, Joint>,
+    #[account(mut)]
+    pub party_a: Signer<'info>,
+    pub party_b: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"joint", joint.party_a.as_ref(), joint.party_b.as_ref()],
+        bump = joint.bump,
+    )]
+    pub joint: Account<'info, Joint>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CoSpend<'info> {
+    #[account(
+        mut,
+        seeds = [b"joint", joint.party_a.as_ref(), joint.party_b.as_ref()],
+        bump = joint.bump,
+    )]
+    pub joint: Account<'info, Joint>,
+    pub first_signer: Signer<'info>,
+    pub second_signer: Signer<'info>,
+    /// CHECK: Any destination the parties agree on
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Joint {
+    pub party_a: Pubkey,
+    pub party_b: Pubkey,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Signer is not a party to this account")]
+    NotAParty,
+    #[msg("Both parties must sign")]
+    MissingConsent,
+    #[msg("A joint account needs two different parties")]
+    DuplicateParty,
+}
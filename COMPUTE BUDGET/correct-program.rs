@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::log::sol_log_compute_units;
+
+declare_id!("CU5ecur222222222222222222222222222222222222");
+
+/// Measured cost of one sha256 round over 32 bytes, including loop overhead
+pub const CU_PER_ROUND: u32 = 120;
+/// Rounds that fit in the default 200,000 CU per-instruction budget
+pub const DEFAULT_BUDGET_ROUNDS: u32 = 1_500;
+/// Hard cap so the worst case still fits in the 1,400,000 CU transaction maximum
+pub const MAX_ROUNDS: u32 = 10_000;
+/// Most commitments one `fold_commitments` call processes, well inside the
+/// default 200,000 CU budget
+pub const MAX_BATCH: usize = 32;
+
+#[program]
+pub mod compute_budget_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let commitment = &mut ctx.accounts.commitment;
+        commitment.owner = ctx.accounts.owner.key();
+        commitment.digest = [0u8; 32];
+        commitment.rounds = 0;
+        commitment.bump = ctx.bumps.commitment;
+        msg!("Commitment initialized");
+        Ok(())
+    }
+
+    ///  SECURE: Cost is bounded and documented
+    /// Above DEFAULT_BUDGET_ROUNDS the client must request more compute
+    /// with ComputeBudgetProgram.setComputeUnitLimit
+    pub fn stretch_commitment(ctx: Context<Stretch>, seed: [u8; 32], rounds: u32) -> Result<()> {
+        //  Reject work that could never fit in any transaction
+        require!(rounds <= MAX_ROUNDS, ErrorCode::TooManyRounds);
+
+        let commitment = &mut ctx.accounts.commitment;
+
+        //  Log remaining CU before and after so cost can be measured
+        sol_log_compute_units();
+        let mut digest = seed;
+        for _ in 0..rounds {
+            digest = hash(&digest).to_bytes();
+        }
+        sol_log_compute_units();
+
+        commitment.digest = digest;
+        commitment.rounds = rounds;
+        msg!(" Stretched commitment over {} rounds", rounds);
+        Ok(())
+    }
+
+    /// Folds commitments from remaining_accounts into `root`, starting from
+    /// the root a previous call returned ([0; 32] for the first call)
+    ///
+    ///  SECURE: At most MAX_BATCH commitments per call. A crank passes the
+    /// returned root back in with the next MAX_BATCH, so a round of any
+    /// size closes in bounded steps
+    pub fn fold_commitments(ctx: Context<Fold>, root: [u8; 32]) -> Result<[u8; 32]> {
+        let count = ctx.remaining_accounts.len().min(MAX_BATCH);
+        let mut root = root;
+
+        //  FIX: Accounts past MAX_BATCH are left for the next call
+        for info in ctx.remaining_accounts[..count].iter() {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::NotACommitment);
+            let commitment = Commitment::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            // Remaining accounts get no seeds constraint: check each is the
+            // commitment PDA of the owner it names
+            let expected = Pubkey::create_program_address(
+                &[b"commitment", commitment.owner.as_ref(), &[commitment.bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| error!(ErrorCode::NotACommitment))?;
+            require_keys_eq!(expected, *info.key, ErrorCode::NotACommitment);
+            root = hashv(&[&root, info.key.as_ref(), &commitment.digest]).to_bytes();
+        }
+
+        msg!(" Folded {} of {} commitments", count, ctx.remaining_accounts.len());
+        Ok(root)
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Commitment::INIT_SPACE,
+        seeds = [b"commitment", owner.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, Commitment>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stretch<'info> {
+    #[account(
+        mut,
+        seeds = [b"commitment", owner.key().as_ref()],
+        bump = commitment.bump,
+        has_one = owner,
+    )]
+    pub commitment: Account<'info, Commitment>,
+    pub owner: Signer<'info>,
+}
+
+/// Commitments are passed in remaining_accounts
+#[derive(Accounts)]
+pub struct Fold {}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Commitment {
+    pub owner: Pubkey,
+    pub digest: [u8; 32],
+    pub rounds: u32,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Requested rounds exceed the maximum that fits in one transaction")]
+    TooManyRounds,
+    #[msg("Account is not a commitment owned by this program")]
+    NotACommitment,
+}
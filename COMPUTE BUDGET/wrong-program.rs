@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+
+declare_id!("CUvu1n1111111111111111111111111111111111111");
+
+#[program]
+pub mod compute_budget_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let commitment = &mut ctx.accounts.commitment;
+        commitment.owner = ctx.accounts.owner.key();
+        commitment.digest = [0u8; 32];
+        commitment.rounds = 0;
+        commitment.bump = ctx.bumps.commitment;
+        msg!("Commitment initialized");
+        Ok(())
+    }
+
+    ///  VULNERABLE: Cost grows with a caller-chosen `rounds`, unbounded!
+    /// Works in a quick test with small values, then fails in production
+    /// once `rounds` pushes past the default 200,000 CU budget
+    pub fn stretch_commitment(ctx: Context<Stretch>, seed: [u8; 32], rounds: u32) -> Result<()> {
+        let commitment = &mut ctx.accounts.commitment;
+
+        //  No upper bound: rounds = 50_000 can never fit, even at 1.4M CU
+        let mut digest = seed;
+        for _ in 0..rounds {
+            digest = hash(&digest).to_bytes();
+        }
+
+        commitment.digest = digest;
+        commitment.rounds = rounds;
+        msg!(" Stretched commitment over {} rounds", rounds);
+        Ok(())
+    }
+
+    /// Folds every commitment in remaining_accounts into one root, e.g. to
+    /// close a round. Read-only, so anyone can crank it
+    ///
+    ///  VULNERABLE: One call per round, however many commitments it has.
+    /// Anyone can open more, until the fold no longer fits in a transaction
+    pub fn fold_commitments(ctx: Context<Fold>) -> Result<[u8; 32]> {
+        let mut root = [0u8; 32];
+
+        //  No cap: cost grows with every account the caller passes
+        for info in ctx.remaining_accounts.iter() {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::NotACommitment);
+            let commitment = Commitment::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            // Remaining accounts get no seeds constraint: check each is the
+            // commitment PDA of the owner it names
+            let expected = Pubkey::create_program_address(
+                &[b"commitment", commitment.owner.as_ref(), &[commitment.bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| error!(ErrorCode::NotACommitment))?;
+            require_keys_eq!(expected, *info.key, ErrorCode::NotACommitment);
+            root = hashv(&[&root, info.key.as_ref(), &commitment.digest]).to_bytes();
+        }
+
+        msg!(" Folded {} commitments", ctx.remaining_accounts.len());
+        Ok(root)
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Commitment::INIT_SPACE,
+        seeds = [b"commitment", owner.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, Commitment>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stretch<'info> {
+    #[account(
+        mut,
+        seeds = [b"commitment", owner.key().as_ref()],
+        bump = commitment.bump,
+        has_one = owner,
+    )]
+    pub commitment: Account<'info, Commitment>,
+    pub owner: Signer<'info>,
+}
+
+/// Commitments are passed in remaining_accounts
+#[derive(Accounts)]
+pub struct Fold {}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Commitment {
+    pub owner: Pubkey,
+    pub digest: [u8; 32],
+    pub rounds: u32,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Account is not a commitment owned by this program")]
+    NotACommitment,
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Rate5ecur2222222222222222222222222222222222");
+
+/// Lowest and highest rate the admin may set, in tokens per point
+pub const MIN_RATE: u64 = 1;
+pub const MAX_RATE: u64 = 100;
+
+#[program]
+pub mod conversion_rate_secure {
+    use super::*;
+
+    /// One-time setup: whoever creates the config is its admin
+    pub fn initialize_rate(ctx: Context<InitializeRate>, rate: u64) -> Result<()> {
+        require!((MIN_RATE..=MAX_RATE).contains(&rate), ErrorCode::RateOutOfBounds);
+
+        let config = &mut ctx.accounts.rate_config;
+        config.admin = ctx.accounts.admin.key();
+        config.rate = rate;
+        config.bump = ctx.bumps.rate_config;
+        msg!("Conversion rate set to {}", rate);
+        Ok(())
+    }
+
+    ///  SECURE: Only the admin changes the rate, and only within bounds
+    pub fn set_rate(ctx: Context<SetRate>, rate: u64) -> Result<()> {
+        require!((MIN_RATE..=MAX_RATE).contains(&rate), ErrorCode::RateOutOfBounds);
+
+        ctx.accounts.rate_config.rate = rate;
+        msg!(" Conversion rate updated to {}", rate);
+        Ok(())
+    }
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.authority = ctx.accounts.authority.key();
+        user.points = 0;
+        user.tokens = 0;
+        Ok(())
+    }
+
+    pub fn add_points(ctx: Context<UpdateUser>, points: u64) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.points = user.points
+            .checked_add(points)
+            .ok_or(ErrorCode::Overflow)?;
+        msg!("Added points: {}", user.points);
+        Ok(())
+    }
+
+    ///  SECURE: No multiplier argument - the rate comes from RateConfig
+    pub fn calculate_tokens(ctx: Context<CalculateTokens>) -> Result<()> {
+        let rate = ctx.accounts.rate_config.rate;
+        let user = &mut ctx.accounts.user;
+
+        let minted = user.points
+            .checked_mul(rate)
+            .ok_or(ErrorCode::Overflow)?;
+
+        user.tokens = user.tokens
+            .checked_add(minted)
+            .ok_or(ErrorCode::Overflow)?;
+        user.points = 0;
+
+        msg!(" Converted at rate {}: {} tokens", rate, user.tokens);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeRate<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RateConfig::INIT_SPACE,
+        seeds = [b"rate_config"],
+        bump
+    )]
+    pub rate_config: Account<'info, RateConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRate<'info> {
+    //  FIX: Only the stored admin can change the rate
+    #[account(mut, seeds = [b"rate_config"], bump = rate_config.bump, has_one = admin)]
+    pub rate_config: Account<'info, RateConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, space = 8 + User::INIT_SPACE)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUser<'info> {
+    #[account(mut, has_one = authority)]
+    pub user: Account<'info, User>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CalculateTokens<'info> {
+    #[account(mut, has_one = authority)]
+    pub user: Account<'info, User>,
+    pub authority: Signer<'info>,
+    //  FIX: The canonical config PDA, not an account the caller made up
+    #[account(seeds = [b"rate_config"], bump = rate_config.bump)]
+    pub rate_config: Account<'info, RateConfig>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct User {
+    pub authority: Pubkey,
+    pub points: u64,
+    pub tokens: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RateConfig {
+    pub admin: Pubkey,
+    /// Tokens minted per point, within `MIN_RATE..=MAX_RATE`
+    pub rate: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Conversion rate out of bounds")]
+    RateOutOfBounds,
+}
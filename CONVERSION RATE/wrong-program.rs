@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Ratevu1n11111111111111111111111111111111111");
+
+#[program]
+pub mod conversion_rate_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.authority = ctx.accounts.authority.key();
+        user.points = 0;
+        user.tokens = 0;
+        Ok(())
+    }
+
+    pub fn add_points(ctx: Context<UpdateUser>, points: u64) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.points = user.points
+            .checked_add(points)
+            .ok_or(ErrorCode::Overflow)?;
+        msg!("Added points: {}", user.points);
+        Ok(())
+    }
+
+    ///  VULNERABLE: The caller picks their own exchange rate!
+    /// The arithmetic is checked - the input is not
+    pub fn calculate_tokens(ctx: Context<UpdateUser>, multiplier: u64) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+
+        //  No overflow here, just a user choosing multiplier = 1_000_000
+        let minted = user.points
+            .checked_mul(multiplier)
+            .ok_or(ErrorCode::Overflow)?;
+
+        user.tokens = user.tokens
+            .checked_add(minted)
+            .ok_or(ErrorCode::Overflow)?;
+        user.points = 0;
+
+        msg!(" Converted at rate {}: {} tokens", multiplier, user.tokens);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, space = 8 + User::INIT_SPACE)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUser<'info> {
+    #[account(mut, has_one = authority)]
+    pub user: Account<'info, User>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct User {
+    pub authority: Pubkey,
+    pub points: u64,
+    pub tokens: u64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
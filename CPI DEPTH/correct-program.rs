@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{get_stack_height, AccountMeta, Instruction},
+    program::invoke,
+};
+use anchor_lang::InstructionData;
+
+declare_id!("Depth5ecur222222222222222222222222222222222");
+
+/// Runtime limit: a top-level instruction runs at height 1 and the deepest
+/// allowed CPI at height 5, i.e. at most four nested invocations
+pub const MAX_STACK_HEIGHT: usize = 5;
+
+/// Longest route this program will ever take on its own. One below the
+/// top-level maximum of 4, so a program calling `route` still fits
+pub const MAX_HOPS: u8 = 3;
+
+#[program]
+pub mod cpi_depth_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.routes_completed = 0;
+        counter.bump = ctx.bumps.counter;
+        msg!("Router initialized");
+        Ok(())
+    }
+
+    ///  SECURE: Checks the whole chain fits before taking the first hop
+    pub fn route(ctx: Context<Route>, hops: u8) -> Result<()> {
+        //  FIX: Flat, documented budget for this program's own nesting
+        require!(hops <= MAX_HOPS, ErrorCode::RouteTooLong);
+
+        //  FIX: Account for however deep the caller already is
+        // This hop runs at `height`; the last one would run at `height + hops`
+        let height = get_stack_height();
+        require!(
+            height + hops as usize <= MAX_STACK_HEIGHT,
+            ErrorCode::CallDepthExceeded
+        );
+
+        if hops == 0 {
+            let counter = &mut ctx.accounts.counter;
+            counter.routes_completed = counter
+                .routes_completed
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+            msg!("Route complete");
+            return Ok(());
+        }
+
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.counter.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.router_program.key(), false),
+            ],
+            data: crate::instruction::Route { hops: hops - 1 }.data(),
+        };
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.counter.to_account_info(),
+                ctx.accounts.router_program.to_account_info(),
+            ],
+        )?;
+
+        // Pick up the inner hop's write before Anchor serializes on exit
+        ctx.accounts.counter.reload()?;
+        msg!("Hop {} returned", hops);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Counter::INIT_SPACE,
+        seeds = [b"counter"],
+        bump
+    )]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Route<'info> {
+    #[account(mut, seeds = [b"counter"], bump = counter.bump)]
+    pub counter: Account<'info, Counter>,
+    /// CHECK: This program, passed so it can invoke itself
+    #[account(address = crate::ID)]
+    pub router_program: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Counter {
+    pub routes_completed: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Route longer than MAX_HOPS")]
+    RouteTooLong,
+    #[msg("Route would exceed the runtime's CPI depth limit")]
+    CallDepthExceeded,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
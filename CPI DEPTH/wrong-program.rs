@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use anchor_lang::InstructionData;
+
+declare_id!("Depthvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod cpi_depth_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.routes_completed = 0;
+        counter.bump = ctx.bumps.counter;
+        msg!("Router initialized");
+        Ok(())
+    }
+
+    ///  VULNERABLE: Nests `hops` CPIs without asking how deep it already is
+    /// Works in a shallow test, then fails deep in the chain once `hops` grows
+    /// or another program calls this one
+    pub fn route(ctx: Context<Route>, hops: u8) -> Result<()> {
+        if hops == 0 {
+            let counter = &mut ctx.accounts.counter;
+            counter.routes_completed = counter.routes_completed.checked_add(1).unwrap();
+            msg!("Route complete");
+            return Ok(());
+        }
+
+        //  No depth check: the runtime rejects the CPI at height 6 with
+        // "Cross-program invocation call depth too deep", after every
+        // earlier hop has already spent its compute
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.counter.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.router_program.key(), false),
+            ],
+            data: crate::instruction::Route { hops: hops - 1 }.data(),
+        };
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.counter.to_account_info(),
+                ctx.accounts.router_program.to_account_info(),
+            ],
+        )?;
+
+        // Pick up the inner hop's write before Anchor serializes on exit
+        ctx.accounts.counter.reload()?;
+        msg!("Hop {} returned", hops);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Counter::INIT_SPACE,
+        seeds = [b"counter"],
+        bump
+    )]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Route<'info> {
+    #[account(mut, seeds = [b"counter"], bump = counter.bump)]
+    pub counter: Account<'info, Counter>,
+    /// CHECK: This program, passed so it can invoke itself
+    #[account(address = crate::ID)]
+    pub router_program: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Counter {
+    pub routes_completed: u64,
+    pub bump: u8,
+}
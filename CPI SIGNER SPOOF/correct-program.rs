@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Spoof5ecur222222222222222222222222222222222");
+
+/// Seed the strategy program uses for the PDA it signs with
+pub const STRATEGY_AUTHORITY_SEED: &[u8] = b"strategy";
+
+#[program]
+pub mod cpi_signer_spoof_secure {
+    use super::*;
+
+    /// Admin sets up the vault and names the strategy program allowed to
+    /// move its funds
+    pub fn initialize(ctx: Context<Initialize>, strategy_program: Pubkey) -> Result<()> {
+        //  FIX: Pin the exact key that must sign - the strategy's own PDA
+        let (strategy_authority, _) =
+            Pubkey::find_program_address(&[STRATEGY_AUTHORITY_SEED], &strategy_program);
+
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.strategy_program = strategy_program;
+        vault.strategy_authority = strategy_authority;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for strategy {}", strategy_program);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: The signer must be the one key only the strategy program can sign for
+    pub fn rebalance(ctx: Context<Rebalance>, amount: u64) -> Result<()> {
+        ctx.accounts.vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Rebalanced {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"vault"], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    #[account(mut, seeds = [b"vault"], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    ///  FIX: Signed AND equal to the strategy program's PDA
+    #[account(address = vault.strategy_authority @ ErrorCode::UnexpectedAuthority)]
+    pub strategy_authority: Signer<'info>,
+    /// CHECK: Where the strategy sends funds
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub admin: Pubkey,
+    pub strategy_program: Pubkey,
+    /// PDA `[STRATEGY_AUTHORITY_SEED]` of `strategy_program`, derived once at init
+    pub strategy_authority: Pubkey,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Signer is not the configured strategy authority")]
+    UnexpectedAuthority,
+}
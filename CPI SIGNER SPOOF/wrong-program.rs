@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Spoofvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod cpi_signer_spoof_vulnerable {
+    use super::*;
+
+    /// Admin sets up the vault and names the strategy program allowed to
+    /// move its funds
+    pub fn initialize(ctx: Context<Initialize>, strategy_program: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.strategy_program = strategy_program;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for strategy {}", strategy_program);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Treats "a PDA signed" as "our strategy program signed"!
+    /// Every program can sign for its own PDAs with invoke_signed
+    pub fn rebalance(ctx: Context<Rebalance>, amount: u64) -> Result<()> {
+        let strategy_authority = &ctx.accounts.strategy_authority;
+
+        //  Only proves SOME program signed for SOME off-curve key
+        require!(
+            !strategy_authority.key().is_on_curve(),
+            ErrorCode::NotAProgramSigner
+        );
+        //  Missing: is this the PDA of vault.strategy_program?
+
+        ctx.accounts.vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Rebalanced {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"vault"], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    #[account(mut, seeds = [b"vault"], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    ///  BUG: Any signer passes, including another program's PDA
+    pub strategy_authority: Signer<'info>,
+    /// CHECK: Where the strategy sends funds
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub admin: Pubkey,
+    pub strategy_program: Pubkey,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Rebalance must be signed by a program")]
+    NotAProgramSigner,
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Cstrvu1n11111111111111111111111111111111111");
+
+/// Minimum stake (0.1 SOL) required to claim rewards or vote
+pub const MIN_BALANCE: u64 = 100_000_000;
+pub const REWARD_PER_CLAIM: u64 = 10;
+
+#[program]
+pub mod custom_constraint_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.reward_points = 0;
+        vault.last_vote = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Stake vault initialized");
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Original instruction: remembers the minimum-balance check
+    pub fn claim_rewards(ctx: Context<Member>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        //  Validation lives in the instruction body
+        require!(vault.balance >= MIN_BALANCE, ErrorCode::TooLow);
+
+        vault.reward_points = vault.reward_points
+            .checked_add(REWARD_PER_CLAIM)
+            .ok_or(ErrorCode::Overflow)?;
+        msg!("Claimed rewards");
+        Ok(())
+    }
+
+    ///  VULNERABLE: Added later, reuses the same accounts struct
+    /// but the minimum-balance check was never copied over!
+    pub fn cast_vote(ctx: Context<Member>, proposal_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        //  Missing: require!(vault.balance >= MIN_BALANCE, ErrorCode::TooLow);
+        vault.last_vote = proposal_id;
+        msg!(" Vote cast on proposal {} without stake check!", proposal_id);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+///  VULNERABLE STRUCT: Nothing here enforces the minimum balance
+#[derive(Accounts)]
+pub struct Member<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub reward_points: u64,
+    pub last_vote: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Vault balance is below the required minimum")]
+    TooLow,
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Deadman5ecur2222222222222222222222222222222");
+
+/// Shortest inactivity period an authority may choose (7 days), so a
+/// missed check-in over a weekend never hands the vault over
+pub const MIN_INACTIVITY_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[program]
+pub mod deadman_switch_secure {
+    use super::*;
+
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        beneficiary: Pubkey,
+        inactivity_period: i64,
+    ) -> Result<()> {
+        require!(inactivity_period >= MIN_INACTIVITY_SECS, ErrorCode::PeriodTooShort);
+
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        //  FIX: A fallback key, fixed by the authority while they still hold theirs
+        vault.beneficiary = beneficiary;
+        vault.balance = 0;
+        vault.inactivity_period = inactivity_period;
+        vault.last_seen_ts = Clock::get()?.unix_timestamp;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}, beneficiary: {}", vault.authority, beneficiary);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        // Anyone can deposit, so a deposit is not proof the authority is alive
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: The authority proves it is still around, pushing the deadline back
+    pub fn check_in(ctx: Context<AuthorityOnly>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.last_seen_ts = Clock::get()?.unix_timestamp;
+        msg!("Authority checked in at {}", vault.last_seen_ts);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<AuthorityOnly>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+        //  FIX: Signing anything is a check-in
+        vault.last_seen_ts = Clock::get()?.unix_timestamp;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: The beneficiary takes the vault once the authority has been
+    /// silent for the whole inactivity period - and not a second sooner
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+        let silent_for = now.checked_sub(vault.last_seen_ts).ok_or(ErrorCode::Overflow)?;
+        require!(silent_for >= vault.inactivity_period, ErrorCode::AuthorityStillActive);
+
+        // `close = beneficiary` moves every lamport, rent included
+        msg!(" Beneficiary claimed {} lamports after {}s of inactivity", vault.balance, silent_for);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AuthorityOnly<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+        //  FIX: Only the key the authority named, only by its signature
+        has_one = beneficiary,
+        close = beneficiary,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    ///  FIX: Who may claim if the authority goes silent
+    pub beneficiary: Pubkey,
+    pub balance: u64,
+    ///  FIX: Last time the authority signed anything
+    pub last_seen_ts: i64,
+    /// Seconds of silence before the beneficiary may claim
+    pub inactivity_period: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Inactivity period is shorter than the minimum")]
+    PeriodTooShort,
+    #[msg("Authority has checked in within the inactivity period")]
+    AuthorityStillActive,
+}
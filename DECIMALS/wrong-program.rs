@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as SplTransfer};
+
+declare_id!("Decvu1n111111111111111111111111111111111111");
+
+///  VULNERABLE: Every mint is assumed to have SOL's 9 decimals
+pub const DECIMALS: u32 = 9;
+
+#[program]
+pub mod decimals_vulnerable {
+    use super::*;
+
+    /// Admin sets up an airdrop of `tokens_per_claim` whole tokens per wallet
+    pub fn create_airdrop(ctx: Context<CreateAirdrop>, tokens_per_claim: u64) -> Result<()> {
+        let airdrop = &mut ctx.accounts.airdrop;
+        airdrop.admin = ctx.accounts.admin.key();
+        airdrop.mint = ctx.accounts.mint.key();
+        airdrop.tokens_per_claim = tokens_per_claim;
+        airdrop.bump = ctx.bumps.airdrop;
+        msg!("Airdrop of {} tokens per claim created", tokens_per_claim);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Scales by 10^9 whatever the mint says!
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        let airdrop = &ctx.accounts.airdrop;
+
+        //  BUG: For a 6-decimal mint this is 1,000x the intended amount
+        let amount = airdrop.tokens_per_claim * 10u64.pow(DECIMALS);
+
+        let mint = airdrop.mint;
+        let seeds = &[b"airdrop".as_ref(), mint.as_ref(), &[airdrop.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.airdrop.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        ctx.accounts.receipt.bump = ctx.bumps.receipt;
+        msg!(" Claimed {} base units", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateAirdrop<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Airdrop::INIT_SPACE,
+        seeds = [b"airdrop", mint.key().as_ref()],
+        bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"airdrop_vault", airdrop.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = airdrop,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(seeds = [b"airdrop", airdrop.mint.as_ref()], bump = airdrop.bump)]
+    pub airdrop: Account<'info, Airdrop>,
+    #[account(mut, seeds = [b"airdrop_vault", airdrop.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    /// One claim per wallet: `init` fails the second time
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + ClaimReceipt::INIT_SPACE,
+        seeds = [b"claim", airdrop.key().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, ClaimReceipt>,
+    #[account(mut, token::mint = airdrop.mint, token::authority = claimant)]
+    pub destination: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Airdrop {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    /// Whole tokens, before scaling to base units
+    pub tokens_per_claim: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimReceipt {
+    pub bump: u8,
+}
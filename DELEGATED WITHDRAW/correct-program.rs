@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Dgate5ecur222222222222222222222222222222222");
+
+/// Longest delegation the authority can grant in one call (30 days)
+pub const MAX_DELEGATION_SECS: i64 = 30 * 24 * 60 * 60;
+
+#[program]
+pub mod delegated_withdraw_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.delegate = None;
+        vault.delegate_expiry_ts = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Every delegation carries an expiry in the near future
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey, expiry_ts: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        //  FIX: Expiry must be in the future, and not too far in it
+        require!(expiry_ts > now, ErrorCode::InvalidExpiry);
+        require!(
+            expiry_ts - now <= MAX_DELEGATION_SECS,
+            ErrorCode::InvalidExpiry
+        );
+
+        let vault = &mut ctx.accounts.vault;
+        vault.delegate = Some(delegate);
+        vault.delegate_expiry_ts = expiry_ts;
+        msg!("Delegate set to {} until {}", delegate, expiry_ts);
+        Ok(())
+    }
+
+    pub fn revoke_delegate(ctx: Context<SetDelegate>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.delegate = None;
+        vault.delegate_expiry_ts = 0;
+        msg!("Delegate revoked");
+        Ok(())
+    }
+
+    ///  SECURE: Delegate rights lapse at delegate_expiry_ts
+    pub fn delegated_withdraw(ctx: Context<DelegatedWithdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        require!(
+            vault.delegate == Some(ctx.accounts.delegate.key()),
+            ErrorCode::NotDelegate
+        );
+        //  FIX: Strictly before expiry; at the expiry second access is gone
+        require!(
+            Clock::get()?.unix_timestamp < vault.delegate_expiry_ts,
+            ErrorCode::DelegationExpired
+        );
+
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Delegate withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelegatedWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    pub delegate: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub delegate: Option<Pubkey>,
+    ///  FIX: Unix timestamp after which `delegate` has no rights
+    pub delegate_expiry_ts: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Signer is not the vault delegate")]
+    NotDelegate,
+    #[msg("Delegation has expired")]
+    DelegationExpired,
+    #[msg("Expiry must be in the future and within the maximum delegation period")]
+    InvalidExpiry,
+}
@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+declare_id!("DepositHook5ecur222222222222222222222222222");
+
+/// Most hook programs the allowlist can hold
+pub const MAX_HOOKS: usize = 4;
+
+#[program]
+pub mod deposit_hook_secure {
+    use super::*;
+
+    /// Admin sets up the vault with the hook programs users may pick from
+    pub fn initialize_config(ctx: Context<InitializeConfig>, allowed_hooks: Vec<Pubkey>) -> Result<()> {
+        require!(allowed_hooks.len() <= MAX_HOOKS, ErrorCode::TooManyHooks);
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.allowed_hooks = allowed_hooks;
+        config.bump = ctx.bumps.config;
+        msg!("Vault initialized with {} allowed hooks", config.allowed_hooks.len());
+        Ok(())
+    }
+
+    /// Admin replaces the allowlist, e.g. to drop a hook found to be unsafe
+    pub fn set_allowed_hooks(ctx: Context<SetAllowedHooks>, allowed_hooks: Vec<Pubkey>) -> Result<()> {
+        require!(allowed_hooks.len() <= MAX_HOOKS, ErrorCode::TooManyHooks);
+
+        ctx.accounts.config.allowed_hooks = allowed_hooks;
+        msg!(" Allowlist updated: {} hooks", ctx.accounts.config.allowed_hooks.len());
+        Ok(())
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.hook = None;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    ///  SECURE: Only an allowlisted program can be stored as a hook
+    pub fn set_hook(ctx: Context<SetHook>, hook: Option<Pubkey>) -> Result<()> {
+        if let Some(hook) = hook {
+            require!(
+                ctx.accounts.config.allowed_hooks.contains(&hook),
+                ErrorCode::HookNotAllowed
+            );
+        }
+        ctx.accounts.position.hook = hook;
+        msg!(" Deposit hook set to {:?}", hook);
+        Ok(())
+    }
+
+    /// Moves `amount` into the treasury, then calls the owner's hook (e.g.
+    /// a loyalty program) with `hook_data` and the remaining accounts
+    ///
+    ///  SECURE: The hook is re-checked against the allowlist, signed for by
+    /// a PDA that holds nothing, and handed only accounts it owns
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, hook_data: Vec<u8>) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        if let Some(hook) = position.hook {
+            //  FIX: Checked at call time too - the admin may have removed it
+            require!(
+                ctx.accounts.config.allowed_hooks.contains(&hook),
+                ErrorCode::HookNotAllowed
+            );
+            require_keys_eq!(ctx.accounts.hook_program.key(), hook, ErrorCode::HookMismatch);
+
+            //  FIX: The hook authority signs, not the treasury; the owner
+            // is passed read-only, without their signature
+            let mut accounts = vec![
+                AccountMeta::new_readonly(ctx.accounts.hook_authority.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.owner.key(), false),
+            ];
+            for account in ctx.remaining_accounts.iter() {
+                //  FIX: Extra accounts are the hook's own state, nothing else
+                require!(!account.is_signer, ErrorCode::ForwardedSigner);
+                require_keys_eq!(*account.owner, hook, ErrorCode::HookAccountNotOwned);
+                accounts.push(AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: false,
+                    is_writable: account.is_writable,
+                });
+            }
+
+            let mut infos = vec![
+                ctx.accounts.hook_authority.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.hook_program.to_account_info(),
+            ];
+            infos.extend_from_slice(ctx.remaining_accounts);
+
+            invoke_signed(
+                &Instruction { program_id: hook, accounts, data: hook_data },
+                &infos,
+                &[&[b"hook-authority", &[ctx.bumps.hook_authority]]],
+            )?;
+        }
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            &[&[b"treasury", &[ctx.bumps.treasury]]],
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedHooks<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetHook<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    /// Holds every depositor's lamports; never passed to a hook
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK: PDA that signs hook calls so hooks can tell they came from
+    /// this vault. Holds no lamports and owns nothing
+    #[account(seeds = [b"hook-authority"], bump)]
+    pub hook_authority: AccountInfo<'info>,
+    /// CHECK: Checked against position.hook and the allowlist in the instruction
+    pub hook_program: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    #[max_len(MAX_HOOKS)]
+    pub allowed_hooks: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub balance: u64,
+    /// Program called after each deposit, if any
+    pub hook: Option<Pubkey>,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in position")]
+    InsufficientFunds,
+    #[msg("Allowlist holds at most 4 hook programs")]
+    TooManyHooks,
+    #[msg("Hook program is not on the allowlist")]
+    HookNotAllowed,
+    #[msg("Hook program account does not match the position's hook")]
+    HookMismatch,
+    #[msg("Hook accounts can't carry a signature")]
+    ForwardedSigner,
+    #[msg("Hook accounts must be owned by the hook program")]
+    HookAccountNotOwned,
+}
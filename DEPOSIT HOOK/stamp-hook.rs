@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+declare_id!("StampHook1111111111111111111111111111111111");
+
+/// A well-behaved deposit hook: a loyalty card that gets one stamp per
+/// deposit. Each card trusts exactly one signer, the vault PDA its owner
+/// registered, so only that vault's deposits count
+#[program]
+pub mod stamp_hook {
+    use super::*;
+
+    /// `vault_signer` is the PDA the vault signs hook calls with
+    pub fn open_card(ctx: Context<OpenCard>, vault_signer: Pubkey) -> Result<()> {
+        let card = &mut ctx.accounts.card;
+        card.owner = ctx.accounts.owner.key();
+        card.vault_signer = vault_signer;
+        card.stamps = 0;
+        card.bump = ctx.bumps.card;
+        Ok(())
+    }
+
+    /// Called by the vault after a deposit.
+    /// Accounts: `[vault_signer (signer), depositor, card (writable)]`
+    pub fn on_deposit(ctx: Context<OnDeposit>) -> Result<()> {
+        let card = &mut ctx.accounts.card;
+        card.stamps = card.stamps.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        msg!(" Stamp {} for {}", card.stamps, card.owner);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenCard<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Card::INIT_SPACE,
+        seeds = [b"card", owner.key().as_ref()],
+        bump
+    )]
+    pub card: Account<'info, Card>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OnDeposit<'info> {
+    pub vault_signer: Signer<'info>,
+    /// CHECK: Only used to find the card
+    pub depositor: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"card", depositor.key().as_ref()],
+        bump = card.bump,
+        has_one = vault_signer,
+    )]
+    pub card: Account<'info, Card>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Card {
+    pub owner: Pubkey,
+    pub vault_signer: Pubkey,
+    pub stamps: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+declare_id!("DepositHookvu1n1111111111111111111111111111");
+
+#[program]
+pub mod deposit_hook_vulnerable {
+    use super::*;
+
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.hook = None;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    ///  VULNERABLE: Stores any program id as the owner's deposit hook
+    pub fn set_hook(ctx: Context<SetHook>, hook: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.position.hook = hook;
+        msg!(" Deposit hook set to {:?}", hook);
+        Ok(())
+    }
+
+    /// Moves `amount` into the treasury, then calls the owner's hook (e.g.
+    /// a loyalty program) with `hook_data` and the remaining accounts
+    ///
+    ///  VULNERABLE: Invokes the stored program with the treasury's signature
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, hook_data: Vec<u8>) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        if let Some(hook) = position.hook {
+            //  BUG: The treasury signs so the hook can tell the call came
+            // from the vault - and the hook can spend that signature
+            let mut accounts = vec![
+                AccountMeta::new(ctx.accounts.treasury.key(), true),
+                AccountMeta::new(ctx.accounts.owner.key(), true),
+            ];
+            //  BUG: Whatever else the caller listed goes along unchecked
+            accounts.extend(ctx.remaining_accounts.iter().map(|a| AccountMeta {
+                pubkey: a.key(),
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            }));
+
+            let mut infos = vec![
+                ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.hook_program.to_account_info(),
+            ];
+            infos.extend_from_slice(ctx.remaining_accounts);
+
+            //  Calling whatever program the owner stored!
+            invoke_signed(
+                &Instruction { program_id: hook, accounts, data: hook_data },
+                &infos,
+                &[&[b"treasury", &[ctx.bumps.treasury]]],
+            )?;
+        }
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            &[&[b"treasury", &[ctx.bumps.treasury]]],
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetHook<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    /// Holds every depositor's lamports
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK:  DANGEROUS - whatever program the position names
+    pub hook_program: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub balance: u64,
+    /// Program called after each deposit, if any
+    pub hook: Option<Pubkey>,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in position")]
+    InsufficientFunds,
+}
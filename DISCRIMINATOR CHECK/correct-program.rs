@@ -0,0 +1,149 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    entrypoint,
+    msg,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+pinocchio_pubkey::declare_id!("Discrim5ecur2222222222222222222222222222222");
+
+/// Instruction tags
+const WITHDRAW: u8 = 0;
+const SET_POINTS: u8 = 1;
+
+/// Seed of the PDA that holds every vault's lamports
+const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Vault layout: discriminator (8) | authority (32) | balance (u64)
+/// Profile layout: discriminator (8) | owner (32) | points (u64)
+///
+/// Same length, different meaning - the only thing telling them apart is
+/// the first 8 bytes.
+const VAULT_LEN: usize = 8 + 32 + 8;
+const PROFILE_LEN: usize = 8 + 32 + 8;
+
+/// `sha256("account:Vault")[..8]` - the bytes Anchor's `#[account]` writes
+/// for a `Vault`, so this program reads accounts Anchor wrote and vice versa.
+/// Derive with: `printf 'account:Vault' | sha256sum | cut -c1-16`
+pub const VAULT_DISCRIMINATOR: [u8; 8] = [0xd3, 0x08, 0xe8, 0x2b, 0x02, 0x98, 0x75, 0x77];
+
+/// `sha256("account:Profile")[..8]`
+pub const PROFILE_DISCRIMINATOR: [u8; 8] = [0xb8, 0x65, 0xa5, 0xbc, 0x5f, 0x3f, 0x7f, 0xbc];
+
+entrypoint!(process_instruction);
+
+///  SECURE: The length and discriminator checks `Account<'info, T>` does after the owner check
+fn check_account_data(data: &[u8], len: usize, discriminator: &[u8; 8]) -> ProgramResult {
+    if data.len() != len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !data.starts_with(discriminator) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Accounts (`Withdraw`):
+///   0. `[writable]`         vault account, owned by this program
+///   1. `[signer, writable]` vault authority, receives the lamports
+///   2. `[writable]`         treasury PDA
+///
+/// Accounts (`SetPoints`):
+///   0. `[writable]` profile account, owned by this program
+///   1. `[signer]`   profile owner
+///
+/// Data: `tag: u8 | amount_or_points: u64` (little-endian)
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (tag, args) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let value = u64::from_le_bytes(
+        args.try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+
+    match *tag {
+        WITHDRAW => withdraw(program_id, accounts, value),
+        SET_POINTS => set_points(program_id, accounts, value),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+///  SECURE: Only an account that starts with the Vault discriminator is a Vault
+fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let [vault, authority, treasury, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !vault.is_owned_by(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let (expected_treasury, _) = pubkey::find_program_address(&[TREASURY_SEED], program_id);
+    if treasury.key() != &expected_treasury {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut data = vault.try_borrow_mut_data()?;
+    //  FIX: A Profile is 48 bytes too - the discriminator is what says "Vault"
+    check_account_data(&data, VAULT_LEN, &VAULT_DISCRIMINATOR)?;
+
+    if &data[8..40] != authority.key().as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let balance = u64::from_le_bytes(
+        data[40..48]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?,
+    );
+    let remaining = balance
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    data[40..48].copy_from_slice(&remaining.to_le_bytes());
+
+    let mut treasury_lamports = treasury.try_borrow_mut_lamports()?;
+    *treasury_lamports = treasury_lamports
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    let mut authority_lamports = authority.try_borrow_mut_lamports()?;
+    *authority_lamports = authority_lamports
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!(" Withdrew from vault");
+    Ok(())
+}
+
+/// Profiles are user-owned: the owner sets any points they like
+///  SECURE: ...on a Profile, never on a Vault that happens to be the same size
+fn set_points(program_id: &Pubkey, accounts: &[AccountInfo], points: u64) -> ProgramResult {
+    let [profile, owner, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !profile.is_owned_by(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut data = profile.try_borrow_mut_data()?;
+    //  FIX: Without this, `points` would overwrite a Vault's `balance`
+    check_account_data(&data, PROFILE_LEN, &PROFILE_DISCRIMINATOR)?;
+    if &data[8..40] != owner.key().as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    data[40..48].copy_from_slice(&points.to_le_bytes());
+
+    msg!("Profile points updated");
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Dispute5ecur2222222222222222222222222222222");
+
+/// How long the arbiter has to rule once the deadline has passed
+pub const ARBITRATION_PERIOD_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[program]
+pub mod dispute_escrow_secure {
+    use super::*;
+
+    /// Buyer locks `amount` lamports for `seller` until `deadline`
+    pub fn create_escrow(
+        ctx: Context<CreateEscrow>,
+        arbiter: Pubkey,
+        amount: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        //  FIX: The arbiter must be neither party, or they judge their own case
+        let buyer = ctx.accounts.buyer.key();
+        let seller = ctx.accounts.seller.key();
+        require!(arbiter != buyer && arbiter != seller, ErrorCode::ArbiterNotIndependent);
+        require!(deadline > Clock::get()?.unix_timestamp, ErrorCode::DeadlineInPast);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.buyer = buyer;
+        escrow.seller = seller;
+        escrow.arbiter = arbiter;
+        escrow.amount = amount;
+        escrow.deadline = deadline;
+        escrow.state = EscrowState::Funded;
+        escrow.bump = ctx.bumps.escrow;
+        msg!("Escrow of {} lamports created, deadline {}", amount, deadline);
+        Ok(())
+    }
+
+    /// Buyer is satisfied and pays the seller
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.state == EscrowState::Funded, ErrorCode::InvalidState);
+
+        escrow.state = EscrowState::Released;
+        let amount = escrow.amount;
+        escrow.sub_lamports(amount)?;
+        ctx.accounts.seller.add_lamports(amount)?;
+
+        msg!(" Released {} lamports to seller", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Funded -> Disputed, and only before the deadline
+    pub fn dispute(ctx: Context<Dispute>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.state == EscrowState::Funded, ErrorCode::InvalidState);
+        //  FIX: A late dispute can't hold up a refund that is already due
+        require!(
+            Clock::get()?.unix_timestamp < escrow.deadline,
+            ErrorCode::DeadlinePassed
+        );
+
+        escrow.state = EscrowState::Disputed;
+        msg!("Escrow disputed by {}", ctx.accounts.party.key());
+        Ok(())
+    }
+
+    ///  SECURE: The arbiter settles a dispute in either party's favour
+    pub fn resolve(ctx: Context<Resolve>, pay_seller: bool) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.state == EscrowState::Disputed, ErrorCode::InvalidState);
+
+        let amount = escrow.amount;
+        escrow.sub_lamports(amount)?;
+        if pay_seller {
+            escrow.state = EscrowState::Released;
+            ctx.accounts.seller.add_lamports(amount)?;
+        } else {
+            escrow.state = EscrowState::Refunded;
+            ctx.accounts.buyer.add_lamports(amount)?;
+        }
+
+        msg!(
+            " Arbiter resolved {} lamports to the {}",
+            amount,
+            if pay_seller { "seller" } else { "buyer" }
+        );
+        Ok(())
+    }
+
+    ///  SECURE: Permissionless refund once nobody can act any more
+    ///
+    /// - `Funded` past the deadline: the buyer never released, nobody disputed
+    /// - `Disputed` past the deadline plus the arbitration period: the arbiter never ruled
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let now = Clock::get()?.unix_timestamp;
+
+        //  FIX: Every non-terminal state has a time after which funds go home
+        let refundable_at = match escrow.state {
+            EscrowState::Funded => escrow.deadline,
+            EscrowState::Disputed => escrow
+                .deadline
+                .checked_add(ARBITRATION_PERIOD_SECS)
+                .ok_or(ErrorCode::Overflow)?,
+            EscrowState::Released | EscrowState::Refunded => {
+                return err!(ErrorCode::InvalidState);
+            }
+        };
+        require!(now >= refundable_at, ErrorCode::RefundNotDue);
+
+        escrow.state = EscrowState::Refunded;
+        let amount = escrow.amount;
+        escrow.sub_lamports(amount)?;
+        ctx.accounts.buyer.add_lamports(amount)?;
+
+        msg!(" Refunded {} lamports to buyer", amount);
+        Ok(())
+    }
+}
+
+/// Escrow lifecycle:
+///
+/// ```text
+/// Funded   ──release──────▶ Released              buyer, any time
+/// Funded   ──dispute──────▶ Disputed              buyer or seller, before the deadline
+/// Funded   ──claim_refund─▶ Refunded              anyone, after the deadline
+/// Disputed ──resolve──────▶ Released | Refunded   arbiter, any time
+/// Disputed ──claim_refund─▶ Refunded              anyone, after deadline + arbitration period
+/// ```
+///
+/// Borsh stores the variant index, so new states go at the end.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+#[repr(u8)]
+pub enum EscrowState {
+    Funded = 0,
+    Disputed = 1,
+    Released = 2,
+    Refunded = 3,
+}
+
+#[derive(Accounts)]
+pub struct CreateEscrow<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", buyer.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: Seller receiving the funds on release
+    pub seller: AccountInfo<'info>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", buyer.key().as_ref(), seller.key().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: Matched to escrow.seller by has_one
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Dispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.buyer.as_ref(), escrow.seller.as_ref()],
+        bump = escrow.bump,
+        constraint = party.key() == escrow.buyer || party.key() == escrow.seller @ ErrorCode::NotAParty,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub party: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Resolve<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", buyer.key().as_ref(), seller.key().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = arbiter,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: Matched to escrow.buyer by has_one
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+    /// CHECK: Matched to escrow.seller by has_one
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+    pub arbiter: Signer<'info>,
+}
+
+/// Permissionless: refunds always go to `escrow.buyer`
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", buyer.key().as_ref(), escrow.seller.as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: Matched to escrow.buyer by has_one
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    ///  FIX: A third party who can always break a deadlock
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    ///  FIX: After this, an undisputed escrow refunds the buyer
+    pub deadline: i64,
+    pub state: EscrowState,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Instruction not allowed in the escrow's current state")]
+    InvalidState,
+    #[msg("Signer is neither the buyer nor the seller")]
+    NotAParty,
+    #[msg("Arbiter must not be the buyer or the seller")]
+    ArbiterNotIndependent,
+    #[msg("Deadline must be in the future")]
+    DeadlineInPast,
+    #[msg("Disputes must be raised before the deadline")]
+    DeadlinePassed,
+    #[msg("Refund is not due yet")]
+    RefundNotDue,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Disputevu1n11111111111111111111111111111111");
+
+#[program]
+pub mod dispute_escrow_vulnerable {
+    use super::*;
+
+    /// Buyer locks `amount` lamports for `seller`
+    pub fn create_escrow(ctx: Context<CreateEscrow>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.buyer = ctx.accounts.buyer.key();
+        escrow.seller = ctx.accounts.seller.key();
+        escrow.amount = amount;
+        escrow.state = EscrowState::Funded;
+        escrow.bump = ctx.bumps.escrow;
+        msg!("Escrow of {} lamports created", amount);
+        Ok(())
+    }
+
+    /// Buyer is satisfied and pays the seller
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.state == EscrowState::Funded, ErrorCode::InvalidState);
+
+        escrow.state = EscrowState::Released;
+        let amount = escrow.amount;
+        escrow.sub_lamports(amount)?;
+        ctx.accounts.seller.add_lamports(amount)?;
+
+        msg!(" Released {} lamports to seller", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Either party can dispute, and nothing ever resolves it!
+    pub fn dispute(ctx: Context<Dispute>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.state == EscrowState::Funded, ErrorCode::InvalidState);
+
+        //  No arbiter, no deadline: Disputed is a dead end
+        escrow.state = EscrowState::Disputed;
+        msg!("Escrow disputed by {}", ctx.accounts.party.key());
+        Ok(())
+    }
+}
+
+/// ```text
+/// Funded ──release──▶ Released
+///    │
+///    └──dispute──▶ Disputed      (no way out)
+/// ```
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+#[repr(u8)]
+pub enum EscrowState {
+    Funded = 0,
+    Disputed = 1,
+    Released = 2,
+}
+
+#[derive(Accounts)]
+pub struct CreateEscrow<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", buyer.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: Seller receiving the funds on release
+    pub seller: AccountInfo<'info>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", buyer.key().as_ref(), seller.key().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: Matched to escrow.seller by has_one
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Dispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.buyer.as_ref(), escrow.seller.as_ref()],
+        bump = escrow.bump,
+        constraint = party.key() == escrow.buyer || party.key() == escrow.seller @ ErrorCode::NotAParty,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub party: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub state: EscrowState,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Instruction not allowed in the escrow's current state")]
+    InvalidState,
+    #[msg("Signer is neither the buyer nor the seller")]
+    NotAParty,
+}
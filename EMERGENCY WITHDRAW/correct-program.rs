@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Emerg5ecur222222222222222222222222222222222");
+
+/// How long an announced emergency withdrawal waits before it can run (2 days)
+pub const EMERGENCY_DELAY_SECS: i64 = 2 * 24 * 60 * 60;
+
+#[program]
+pub mod emergency_withdraw_secure {
+    use super::*;
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+        msg!("Admin: {}", config.admin);
+        Ok(())
+    }
+
+    pub fn open_vault(ctx: Context<OpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault opened for owner: {}", vault.owner);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Step 1 of 2. Records what will be moved, where, and the
+    /// earliest time it can happen; nothing moves yet
+    pub fn announce_emergency_withdraw(
+        ctx: Context<AnnounceEmergencyWithdraw>,
+        amount: u64,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let announcement = &mut ctx.accounts.announcement;
+        announcement.vault = ctx.accounts.vault.key();
+        announcement.destination = destination;
+        announcement.amount = amount;
+        //  FIX: Users get EMERGENCY_DELAY_SECS to see this and withdraw
+        announcement.executable_at = now.checked_add(EMERGENCY_DELAY_SECS).ok_or(ErrorCode::Overflow)?;
+        announcement.bump = ctx.bumps.announcement;
+
+        msg!(
+            "Emergency withdrawal of {} lamports from {} announced, executable at {}",
+            amount,
+            ctx.accounts.vault.owner,
+            announcement.executable_at
+        );
+        Ok(())
+    }
+
+    ///  SECURE: Step 2 of 2. Only after the delay, only to the announced
+    /// destination, and never more than the vault still holds
+    pub fn execute_emergency_withdraw(ctx: Context<ExecuteEmergencyWithdraw>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let announcement = &ctx.accounts.announcement;
+
+        //  FIX: Clock-gated: rejected until the announced time
+        require!(now >= announcement.executable_at, ErrorCode::TimelockActive);
+
+        // Users may have exited during the delay; take what is left, up to the announced amount
+        let vault = &mut ctx.accounts.vault;
+        let amount = announcement.amount.min(vault.balance);
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        //  `close = admin` removes the announcement: each one runs once
+        msg!(" Emergency withdrew {} lamports from {}", amount, vault.owner);
+        Ok(())
+    }
+
+    /// The admin can always back out of an announcement
+    pub fn cancel_emergency_withdraw(_ctx: Context<CancelEmergencyWithdraw>) -> Result<()> {
+        msg!("Emergency withdrawal cancelled");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AnnounceEmergencyWithdraw<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    #[account(seeds = [b"vault", vault.owner.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    ///  FIX: One pending announcement per vault, visible on-chain to its owner
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + EmergencyAnnouncement::INIT_SPACE,
+        seeds = [b"emergency", vault.key().as_ref()],
+        bump
+    )]
+    pub announcement: Account<'info, EmergencyAnnouncement>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteEmergencyWithdraw<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"vault", vault.owner.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency", vault.key().as_ref()],
+        bump = announcement.bump,
+        has_one = vault,
+        has_one = destination,
+    )]
+    pub announcement: Account<'info, EmergencyAnnouncement>,
+    /// CHECK: Must be the destination that was announced
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelEmergencyWithdraw<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency", announcement.vault.as_ref()],
+        bump = announcement.bump,
+    )]
+    pub announcement: Account<'info, EmergencyAnnouncement>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+/// A pending emergency withdrawal, public from the moment it is announced
+#[account]
+#[derive(InitSpace)]
+pub struct EmergencyAnnouncement {
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// Unix timestamp from which `execute_emergency_withdraw` is allowed
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Emergency withdrawal timelock has not elapsed")]
+    TimelockActive,
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+// SHARED UTILS/safe_cast.rs
+mod safe_cast;
+
+declare_id!("Epoch5ecur222222222222222222222222222222222");
+
+#[program]
+pub mod epoch_accrual_secure {
+    use super::*;
+
+    /// Admin sets the reward rate (points per staked SOL per second) and
+    /// schedules when rewards start accruing
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        reward_rate: u64,
+        rewards_start: i64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.reward_rate = reward_rate;
+        config.rewards_start = rewards_start;
+        config.bump = ctx.bumps.config;
+        msg!("Rewards start at {}, rate {}", rewards_start, reward_rate);
+        Ok(())
+    }
+
+    /// The new rate applies to every second not yet accrued: positions that
+    /// haven't claimed since the change are paid the new rate for that time
+    pub fn set_reward_rate(ctx: Context<SetRewardRate>, reward_rate: u64) -> Result<()> {
+        ctx.accounts.config.reward_rate = reward_rate;
+        msg!(" Reward rate set to {}", reward_rate);
+        Ok(())
+    }
+
+    /// Opens a position with its first stake
+    pub fn open_position(ctx: Context<OpenPosition>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.position.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.staked = amount;
+        position.rewards = 0;
+        // The start is applied in `accrue`, so last_claim is never in the future
+        position.last_claim = Clock::get()?.unix_timestamp;
+        position.bump = ctx.bumps.position;
+        msg!(" Staked {} lamports", amount);
+        Ok(())
+    }
+
+    /// Books everything accrued since the last claim
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        accrue(&mut ctx.accounts.position, &ctx.accounts.config)?;
+        msg!(" Rewards: {}", ctx.accounts.position.rewards);
+        Ok(())
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        // Settle at the old stake before it changes
+        accrue(&mut ctx.accounts.position, &ctx.accounts.config)?;
+
+        let position = &mut ctx.accounts.position;
+        position.staked = position
+            .staked
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        position.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(" Unstaked {} lamports", amount);
+        Ok(())
+    }
+}
+
+/// Seconds of accrual between `from` and `now`, clamped at zero. A `from`
+/// in the future - rewards not started, or a clock that stepped back -
+/// means no time has passed yet
+fn elapsed_since(from: i64, now: i64) -> Result<u64> {
+    //  FIX: Checked, then clamped, then converted with a check
+    let elapsed = now.checked_sub(from).ok_or(ErrorCode::Overflow)?;
+    safe_cast::i64_to_u64(elapsed.max(0))
+}
+
+///  SECURE: Pays `staked * elapsed * rate` for time since the later of
+/// last_claim and rewards_start, and never moves last_claim backwards
+fn accrue(position: &mut Position, config: &Config) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    //  FIX: Nothing accrues before the start, whatever last_claim says
+    let from = position.last_claim.max(config.rewards_start);
+    let elapsed = elapsed_since(from, now)?;
+
+    let reward = (position.staked as u128)
+        .checked_mul(elapsed as u128)
+        .and_then(|v| v.checked_mul(config.reward_rate as u128))
+        .ok_or(ErrorCode::Overflow)?
+        / LAMPORTS_PER_SOL as u128;
+    position.rewards = position
+        .rewards
+        .checked_add(safe_cast::u128_to_u64(reward)?)
+        .ok_or(ErrorCode::Overflow)?;
+
+    //  FIX: If the clock is behind last_claim, keep last_claim - moving it
+    // back would pay the same seconds again once the clock catches up
+    position.last_claim = position.last_claim.max(now);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardRate<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub reward_rate: u64,
+    pub rewards_start: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub staked: u64,
+    pub rewards: u64,
+    pub last_claim: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in position")]
+    InsufficientFunds,
+}
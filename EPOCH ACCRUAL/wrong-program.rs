@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+declare_id!("Epochvu1n1111111111111111111111111111111111");
+
+/// Reward points per staked SOL per second
+pub const REWARD_RATE: u64 = 1;
+
+#[program]
+pub mod epoch_accrual_vulnerable {
+    use super::*;
+
+    /// Admin schedules when rewards start accruing
+    pub fn initialize_config(ctx: Context<InitializeConfig>, rewards_start: i64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.rewards_start = rewards_start;
+        config.bump = ctx.bumps.config;
+        msg!("Rewards start at {}", rewards_start);
+        Ok(())
+    }
+
+    /// Opens a position with its first stake
+    pub fn open_position(ctx: Context<OpenPosition>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.position.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.staked = amount;
+        position.rewards = 0;
+        // Nothing accrues before the start - which puts last_claim in the
+        // future for anyone who stakes early
+        position.last_claim = now.max(ctx.accounts.config.rewards_start);
+        position.bump = ctx.bumps.position;
+        msg!(" Staked {} lamports, accruing from {}", amount, position.last_claim);
+        Ok(())
+    }
+
+    /// Books everything accrued since the last claim
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        accrue(&mut ctx.accounts.position)?;
+        msg!(" Rewards: {}", ctx.accounts.position.rewards);
+        Ok(())
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        accrue(&mut ctx.accounts.position)?;
+
+        let position = &mut ctx.accounts.position;
+        position.staked = position
+            .staked
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        position.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(" Unstaked {} lamports", amount);
+        Ok(())
+    }
+}
+
+///  VULNERABLE: Assumes the clock has moved forward since last_claim
+fn accrue(position: &mut Position) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    //  BUG: Before rewards_start, or if the cluster clock steps back, this
+    // is negative - and `as u64` turns -1 into 18446744073709551615 seconds
+    let elapsed = (now - position.last_claim) as u64;
+
+    // The product is done carefully in u128...
+    let reward = position.staked as u128 * elapsed as u128 * REWARD_RATE as u128
+        / LAMPORTS_PER_SOL as u128;
+    // ...and cast back without a check
+    position.rewards = position
+        .rewards
+        .checked_add(reward as u64)
+        .ok_or(ErrorCode::Overflow)?;
+    position.last_claim = now;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub rewards_start: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub staked: u64,
+    pub rewards: u64,
+    pub last_claim: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in position")]
+    InsufficientFunds,
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+declare_id!("ErrMsg5ecur22222222222222222222222222222222");
+
+#[program]
+pub mod error_messages_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, withdraw_limit: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.withdraw_limit = withdraw_limit;
+        vault.paused = false;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.vault.paused = paused;
+        msg!("Vault paused: {}", paused);
+        Ok(())
+    }
+
+    ///  SECURE: Every failure mode has its own error, so the caller knows what to fix
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        //  FIX: Authority and pause are account constraints, each with its own `@` error;
+        // the argument checks below name exactly what was wrong with `amount`
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        require!(amount <= vault.withdraw_limit, ErrorCode::ExceedsWithdrawLimit);
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+
+        vault.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+        //  FIX: Replaces the default ConstraintHasOne with a named error
+        has_one = authority @ ErrorCode::NotVaultAuthority,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+        //  FIX: Checked in declaration order - the authority first, then the pause
+        has_one = authority @ ErrorCode::NotVaultAuthority,
+        constraint = !vault.paused @ ErrorCode::VaultPaused,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    /// Most a single withdrawal may move
+    pub withdraw_limit: u64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+///  SECURE: One variant per failure mode. Codes are 6000 + position, so new
+/// variants go at the end - reordering changes codes clients already match on.
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Signer is not this vault's authority")]
+    NotVaultAuthority,
+    #[msg("Vault is paused")]
+    VaultPaused,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Amount exceeds the vault's withdraw limit")]
+    ExceedsWithdrawLimit,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+declare_id!("ErrMsgvu1n111111111111111111111111111111111");
+
+#[program]
+pub mod error_messages_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, withdraw_limit: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.withdraw_limit = withdraw_limit;
+        vault.paused = false;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Unauthorized)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.authority == ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+        vault.paused = paused;
+        msg!("Vault paused: {}", paused);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Five different failures, one error - the caller can't tell which!
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        //  Wrong key, paused vault, zero amount, over the limit, not enough
+        // funds: every one of them reports "Unauthorized"
+        require!(vault.authority == ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+        require!(!vault.paused, ErrorCode::Unauthorized);
+        require!(amount > 0, ErrorCode::Unauthorized);
+        require!(amount <= vault.withdraw_limit, ErrorCode::Unauthorized);
+        require!(amount <= vault.balance, ErrorCode::Unauthorized);
+
+        vault.balance -= amount;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    /// Most a single withdrawal may move
+    pub withdraw_limit: u64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+///  VULNERABLE: One variant for every failure mode
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Unauthorized")]
+    Unauthorized,
+}
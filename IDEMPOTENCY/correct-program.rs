@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Dedup5ecur222222222222222222222222222222222");
+
+#[program]
+pub mod idempotency_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, relayer: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.relayer = relayer;
+        config.bump = ctx.bumps.config;
+        msg!("Relayer set to {}", relayer);
+        Ok(())
+    }
+
+    pub fn register(ctx: Context<Register>) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.owner = ctx.accounts.owner.key();
+        user.balance = 0;
+        user.last_op_id = 0;
+        user.bump = ctx.bumps.user;
+        msg!("User registered: {}", user.owner);
+        Ok(())
+    }
+
+    ///  SECURE: Each op_id is processed at most once per user
+    /// The off-chain source assigns strictly increasing ids; anything at or
+    /// below the last processed id is a retry or replay and is rejected
+    pub fn credit_deposit(ctx: Context<CreditDeposit>, op_id: u64, amount: u64) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+
+        //  FIX: Reject duplicates before touching the balance
+        require!(op_id > user.last_op_id, ErrorCode::DuplicateOperation);
+
+        user.balance = user.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        //  FIX: Record the id in the same instruction that applies it
+        user.last_op_id = op_id;
+
+        msg!(" Credited op {} for {}: {}", op_id, user.owner, amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Register<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + User::INIT_SPACE,
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreditDeposit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = relayer)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"user", user.owner.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    pub relayer: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub relayer: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct User {
+    pub owner: Pubkey,
+    pub balance: u64,
+    ///  FIX: Highest operation id already applied to this user
+    pub last_op_id: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Operation already processed")]
+    DuplicateOperation,
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Dedupvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod idempotency_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, relayer: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.relayer = relayer;
+        config.bump = ctx.bumps.config;
+        msg!("Relayer set to {}", relayer);
+        Ok(())
+    }
+
+    pub fn register(ctx: Context<Register>) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.owner = ctx.accounts.owner.key();
+        user.balance = 0;
+        user.bump = ctx.bumps.user;
+        msg!("User registered: {}", user.owner);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Relayer credits an off-chain deposit with no dedup!
+    /// A retried or replayed submission of the same op_id credits twice
+    pub fn credit_deposit(ctx: Context<CreditDeposit>, op_id: u64, amount: u64) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+
+        //  op_id is logged but never checked or recorded
+        // Network timeout -> relayer retries -> balance credited again
+        user.balance = user.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        msg!(" Credited op {} for {}: {}", op_id, user.owner, amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Register<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + User::INIT_SPACE,
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreditDeposit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = relayer)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"user", user.owner.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+    pub relayer: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub relayer: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct User {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Need5ecur2222222222222222222222222222222222");
+
+#[program]
+pub mod init_if_needed_secure {
+    use super::*;
+
+    ///  SECURE: `init` can only succeed once
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.admin = ctx.accounts.payer.key();
+        treasury.bump = ctx.bumps.treasury;
+        msg!("Treasury admin set to {}", treasury.admin);
+        Ok(())
+    }
+
+    ///  SECURE: init_if_needed kept for a per-user account, guarded
+    /// Seeds include the depositor, so nobody else can reach this account,
+    /// and the `is_initialized` flag keeps repeat calls from resetting it
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let receipt = &mut ctx.accounts.receipt;
+        //  FIX: Set identity fields on the first call only
+        if !receipt.is_initialized {
+            receipt.depositor = ctx.accounts.depositor.key();
+            receipt.total = 0;
+            receipt.bump = ctx.bumps.receipt;
+            receipt.is_initialized = true;
+        }
+        receipt.total = receipt.total.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.treasury.sub_lamports(amount)?;
+        ctx.accounts.admin.add_lamports(amount)?;
+        msg!(" Admin withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+///  FIX: Plain `init` - a second call fails with "already in use"
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [b"receipt", depositor.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump, has_one = admin)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub admin: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Receipt {
+    pub depositor: Pubkey,
+    pub total: u64,
+    pub is_initialized: bool,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Needvu1n11111111111111111111111111111111111");
+
+#[program]
+pub mod init_if_needed_vulnerable {
+    use super::*;
+
+    ///  VULNERABLE: Runs on EVERY call, not just the first
+    /// `init_if_needed` skips creating the account when it already exists,
+    /// but the handler body still executes and overwrites the admin
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        //  Second caller becomes the new admin
+        treasury.admin = ctx.accounts.payer.key();
+        treasury.bump = ctx.bumps.treasury;
+
+        msg!("Treasury admin set to {}", treasury.admin);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.treasury.sub_lamports(amount)?;
+        ctx.accounts.admin.add_lamports(amount)?;
+        msg!(" Admin withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+///  VULNERABLE: init_if_needed on a singleton with no initialized check
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump, has_one = admin)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub admin: Pubkey,
+    pub bump: u8,
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{self, load_instruction_at_checked};
+
+declare_id!("Sysvar5ecur22222222222222222222222222222222");
+
+#[program]
+pub mod instruction_introspection_secure {
+    use super::*;
+
+    /// Admin opens a limited ticket drop at a fixed price
+    pub fn create_drop(ctx: Context<CreateDrop>, price: u64, supply: u32) -> Result<()> {
+        let drop = &mut ctx.accounts.drop;
+        drop.admin = ctx.accounts.admin.key();
+        drop.price = price;
+        drop.remaining = supply;
+        drop.bump = ctx.bumps.drop;
+        msg!("Drop created: {} tickets at {} lamports", supply, price);
+        Ok(())
+    }
+
+    ///  SECURE: Exactly one call to this program per transaction
+    pub fn mint_ticket(ctx: Context<MintTicket>) -> Result<()> {
+        //  FIX: Top-level only - the sysvar lists top-level instructions,
+        // so a program calling us in a loop would never show up in it
+        require!(
+            get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            ErrorCode::CpiNotAllowed
+        );
+
+        //  FIX: Count every instruction in the transaction that targets us
+        let calls = count_program_instructions(&ctx.accounts.instructions_sysvar)?;
+        require!(calls == 1, ErrorCode::StackedInstructions);
+
+        require!(ctx.accounts.drop.remaining > 0, ErrorCode::SoldOut);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.drop.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, ctx.accounts.drop.price)?;
+
+        let drop = &mut ctx.accounts.drop;
+        drop.remaining -= 1;
+        msg!(" Ticket minted, {} left", drop.remaining);
+        Ok(())
+    }
+}
+
+/// Walks the transaction's top-level instructions and counts the ones
+/// addressed to this program. `load_instruction_at_checked` returns
+/// `InvalidArgument` once `index` runs past the last instruction
+fn count_program_instructions(instructions_sysvar: &AccountInfo) -> Result<usize> {
+    let mut calls = 0;
+    let mut index = 0;
+    loop {
+        match load_instruction_at_checked(index, instructions_sysvar) {
+            Ok(ix) => {
+                if ix.program_id == crate::ID {
+                    calls += 1;
+                }
+                index += 1;
+            }
+            Err(ProgramError::InvalidArgument) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(calls)
+}
+
+#[derive(Accounts)]
+pub struct CreateDrop<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Drop::INIT_SPACE,
+        seeds = [b"drop"],
+        bump
+    )]
+    pub drop: Account<'info, Drop>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintTicket<'info> {
+    #[account(mut, seeds = [b"drop"], bump = drop.bump)]
+    pub drop: Account<'info, Drop>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    ///  FIX: The real instructions sysvar, not a look-alike account
+    /// CHECK: Address constraint pins it to the sysvar
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Drop {
+    pub admin: Pubkey,
+    pub price: u64,
+    pub remaining: u32,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Drop is sold out")]
+    SoldOut,
+    #[msg("Only one mint_ticket instruction per transaction")]
+    StackedInstructions,
+    #[msg("mint_ticket cannot be called through CPI")]
+    CpiNotAllowed,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Sysvarvu1n111111111111111111111111111111111");
+
+#[program]
+pub mod instruction_introspection_vulnerable {
+    use super::*;
+
+    /// Admin opens a limited ticket drop at a fixed price
+    pub fn create_drop(ctx: Context<CreateDrop>, price: u64, supply: u32) -> Result<()> {
+        let drop = &mut ctx.accounts.drop;
+        drop.admin = ctx.accounts.admin.key();
+        drop.price = price;
+        drop.remaining = supply;
+        drop.bump = ctx.bumps.drop;
+        msg!("Drop created: {} tickets at {} lamports", supply, price);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Meant to be one ticket per transaction, never checked!
+    /// A bot packs many mint_ticket instructions into one transaction
+    pub fn mint_ticket(ctx: Context<MintTicket>) -> Result<()> {
+        require!(ctx.accounts.drop.remaining > 0, ErrorCode::SoldOut);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.drop.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, ctx.accounts.drop.price)?;
+
+        let drop = &mut ctx.accounts.drop;
+        drop.remaining -= 1;
+        msg!(" Ticket minted, {} left", drop.remaining);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateDrop<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Drop::INIT_SPACE,
+        seeds = [b"drop"],
+        bump
+    )]
+    pub drop: Account<'info, Drop>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintTicket<'info> {
+    #[account(mut, seeds = [b"drop"], bump = drop.bump)]
+    pub drop: Account<'info, Drop>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Drop {
+    pub admin: Pubkey,
+    pub price: u64,
+    pub remaining: u32,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Drop is sold out")]
+    SoldOut,
+}
@@ -0,0 +1,109 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    entrypoint,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pinocchio_pubkey::declare_id!("DataLen5ecur2222222222222222222222222222222");
+
+/// Instruction tag for `UpdateConfig`
+const UPDATE_CONFIG: u8 = 0;
+
+/// Config account layout: admin (32) | fee_bps (u16) | max_amount (u64)
+const CONFIG_LEN: usize = 32 + 2 + 8;
+
+entrypoint!(process_instruction);
+
+/// `UpdateConfig` arguments, as they appear after the tag
+struct UpdateConfigArgs {
+    fee_bps: u16,
+    max_amount: u64,
+}
+
+impl UpdateConfigArgs {
+    /// fee_bps (u16) | max_amount (u64)
+    const LEN: usize = 2 + 8;
+
+    ///  SECURE: Exactly `LEN` bytes or `InvalidInstructionData`, never a panic
+    fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (fee_bps, max_amount) = data.split_at(2);
+        Ok(Self {
+            fee_bps: u16::from_le_bytes(
+                fee_bps
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            ),
+            max_amount: u64::from_le_bytes(
+                max_amount
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            ),
+        })
+    }
+}
+
+///  SECURE: Checks the data length before reading a single field
+///
+/// Accounts:
+///   0. `[writable]` config account, owned by this program
+///   1. `[signer]`   config admin
+///
+/// Data: `tag: u8 (0) | fee_bps: u16 | max_amount: u64` (little-endian)
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [config, admin, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    //  FIX: Empty data is an error, not an index panic
+    let (tag, args) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match *tag {
+        UPDATE_CONFIG => {
+            //  FIX: Too short and too long are both rejected
+            let args = UpdateConfigArgs::try_from_bytes(args)?;
+            update_config(program_id, config, admin, args.fee_bps, args.max_amount)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn update_config(
+    program_id: &Pubkey,
+    config: &AccountInfo,
+    admin: &AccountInfo,
+    fee_bps: u16,
+    max_amount: u64,
+) -> ProgramResult {
+    if !config.is_owned_by(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !admin.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut data = config.try_borrow_mut_data()?;
+    if data.len() != CONFIG_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if &data[0..32] != admin.key().as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    data[32..34].copy_from_slice(&fee_bps.to_le_bytes());
+    data[34..42].copy_from_slice(&max_amount.to_le_bytes());
+
+    msg!(" Config updated");
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    entrypoint,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pinocchio_pubkey::declare_id!("DataLenvu1n11111111111111111111111111111111");
+
+/// Instruction tag for `UpdateConfig`
+const UPDATE_CONFIG: u8 = 0;
+
+/// Config account layout: admin (32) | fee_bps (u16) | max_amount (u64)
+const CONFIG_LEN: usize = 32 + 2 + 8;
+
+entrypoint!(process_instruction);
+
+///  VULNERABLE: Reads fixed offsets without checking the data is that long!
+///
+/// Accounts:
+///   0. `[writable]` config account, owned by this program
+///   1. `[signer]`   config admin
+///
+/// Data: `tag: u8 (0) | fee_bps: u16 | max_amount: u64` (little-endian)
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [config, admin, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    //  Empty data panics here
+    match instruction_data[0] {
+        UPDATE_CONFIG => {
+            //  Anything shorter than 11 bytes panics here - and anything
+            // longer is accepted, the extra bytes silently ignored
+            let fee_bps = u16::from_le_bytes(instruction_data[1..3].try_into().unwrap());
+            let max_amount = u64::from_le_bytes(instruction_data[3..11].try_into().unwrap());
+
+            update_config(program_id, config, admin, fee_bps, max_amount)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn update_config(
+    program_id: &Pubkey,
+    config: &AccountInfo,
+    admin: &AccountInfo,
+    fee_bps: u16,
+    max_amount: u64,
+) -> ProgramResult {
+    if !config.is_owned_by(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !admin.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut data = config.try_borrow_mut_data()?;
+    if data.len() != CONFIG_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if &data[0..32] != admin.key().as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    data[32..34].copy_from_slice(&fee_bps.to_le_bytes());
+    data[34..42].copy_from_slice(&max_amount.to_le_bytes());
+
+    msg!(" Config updated");
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+
+declare_id!("MinGatevu1n11111111111111111111111111111111");
+
+#[program]
+pub mod min_balance_gate_vulnerable {
+    use super::*;
+
+    /// Admin sets the balance an account must hold to earn points
+    pub fn initialize_config(ctx: Context<InitializeConfig>, min_balance: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.min_balance = min_balance;
+        config.bump = ctx.bumps.config;
+        msg!("Minimum balance to earn points: {}", min_balance);
+        Ok(())
+    }
+
+    pub fn set_min_balance(ctx: Context<SetMinBalance>, min_balance: u64) -> Result<()> {
+        ctx.accounts.config.min_balance = min_balance;
+        msg!("Minimum balance to earn points: {}", min_balance);
+        Ok(())
+    }
+
+    pub fn open_account(ctx: Context<OpenAccount>) -> Result<()> {
+        let user_account = &mut ctx.accounts.user_account;
+        user_account.owner = ctx.accounts.owner.key();
+        user_account.balance = 0;
+        user_account.points = 0;
+        user_account.bump = ctx.bumps.user_account;
+        msg!("User account opened");
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.user_account.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let user_account = &mut ctx.accounts.user_account;
+        user_account.balance = user_account.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let user_account = &mut ctx.accounts.user_account;
+        user_account.balance = user_account
+            .balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        user_account.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Awards points whatever the account holds!
+    pub fn add_points(ctx: Context<AddPoints>, points: u64) -> Result<()> {
+        let user_account = &mut ctx.accounts.user_account;
+
+        //  BUG: `config.min_balance` is set, stored and never read. An empty
+        // account earns exactly what a fully funded one does
+        user_account.points = user_account.points.checked_add(points).ok_or(ErrorCode::Overflow)?;
+        msg!(" Added {} points", points);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinBalance<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenAccount<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + UserAccount::INIT_SPACE,
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump = user_account.bump,
+        has_one = owner,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump = user_account.bump,
+        has_one = owner,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// The admin awards points (for activity, referrals, ...) to any account
+#[derive(Accounts)]
+pub struct AddPoints<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"user", user_account.owner.as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub min_balance: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserAccount {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub points: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in account")]
+    InsufficientFunds,
+}
@@ -1,5 +1,19 @@
 use anchor_lang::prelude::*;
 
+// SHARED UTILS/lamports.rs
+mod lamports;
+use lamports::Lamports;
+// Anchor's `sub_lamports`/`add_lamports`, whose trait name the type above shadows
+use anchor_lang::Lamports as _;
+
+// SHARED UTILS/logging.rs
+mod logging;
+use logging::{audit_msg, log_msg};
+
+// SHARED UTILS/balance.rs
+mod balance;
+use balance::require_sufficient_balance;
+
 declare_id!("Secu222222222222222222222222222222222222222");
 
 #[program]
@@ -9,9 +23,9 @@ pub mod missing_signer_secure {
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         vault.authority = ctx.accounts.authority.key();
-        vault.balance = 0;
+        vault.balance = Lamports::ZERO;
         vault.bump = ctx.bumps.vault;
-        msg!("Vault initialized for authority: {}", vault.authority);
+        log_msg!("Vault initialized for authority: {}", vault.authority);
         Ok(())
     }
 
@@ -27,8 +41,14 @@ pub mod missing_signer_secure {
         );
         anchor_lang::system_program::transfer(cpi_context, amount)?;
         
-        vault.balance = vault.balance.checked_add(amount).unwrap();
-        msg!("Deposited {} lamports", amount);
+        vault.balance = accounting::credit(vault.balance, amount)?;
+        emit!(Deposited {
+            vault: vault.key(),
+            depositor: ctx.accounts.user.key(),
+            amount,
+            balance: vault.balance.get(),
+        });
+        log_msg!("Deposited {} lamports", amount);
         Ok(())
     }
 
@@ -42,7 +62,8 @@ pub mod missing_signer_secure {
             ErrorCode::Unauthorized
         );
         
-        require!(vault.balance >= amount, ErrorCode::InsufficientFunds);
+        //  Computed before any lamports move: fails with InsufficientFunds
+        let new_balance = accounting::debit(vault.balance, amount)?;
         
         let authority_key = vault.authority;
         let seeds = &[
@@ -62,12 +83,104 @@ pub mod missing_signer_secure {
         );
         anchor_lang::system_program::transfer(cpi_context, amount)?;
         
-        vault.balance = vault.balance.checked_sub(amount).unwrap();
-        msg!(" Securely withdrew {} lamports", amount);
+        vault.balance = new_balance;
+        emit!(Withdrawn {
+            vault: vault.key(),
+            destination: ctx.accounts.user.key(),
+            amount,
+            balance: new_balance.get(),
+        });
+        audit_msg!(" Securely withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    /// Empties the vault down to its rent-exempt minimum in one instruction.
+    /// Pays out everything above rent, including lamports that bypassed
+    /// `deposit`, and zeroes the ledger; the vault itself stays open
+    pub fn withdraw_all(ctx: Context<SecureWithdraw>) -> Result<()> {
+        require!(
+            ctx.accounts.vault.authority == ctx.accounts.authority.key(),
+            ErrorCode::Unauthorized
+        );
+
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+        let amount = accounting::withdrawable(vault_info.lamports(), rent_exempt)?;
+
+        let tracked = ctx.accounts.vault.balance;
+        if tracked != amount {
+            emit!(BalanceDrift {
+                vault: vault_info.key(),
+                tracked: tracked.get(),
+                actual: amount.get(),
+            });
+        }
+
+        // The vault is owned by this program, so it can debit it directly
+        ctx.accounts.vault.sub_lamports(amount.get())?;
+        ctx.accounts.user.add_lamports(amount.get())?;
+
+        ctx.accounts.vault.balance = Lamports::ZERO;
+        emit!(Withdrawn {
+            vault: vault_info.key(),
+            destination: ctx.accounts.user.key(),
+            amount: amount.get(),
+            balance: 0,
+        });
+        audit_msg!(" Withdrew all {} lamports", amount);
+        Ok(())
+    }
+
+    /// Lamports can reach the vault without `deposit` (a plain system
+    /// transfer, an airdrop), leaving `vault.balance` below what the account
+    /// actually holds. Reports the drift and resets the ledger to match
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<()> {
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+        let actual = Lamports(vault_info.lamports().saturating_sub(rent_exempt));
+
+        let vault = &mut ctx.accounts.vault;
+        let tracked = vault.balance;
+        if tracked != actual {
+            emit!(BalanceDrift {
+                vault: vault_info.key(),
+                tracked: tracked.get(),
+                actual: actual.get(),
+            });
+            vault.balance = actual;
+            audit_msg!(" Reconciled balance {} -> {}", tracked, actual);
+        }
         Ok(())
     }
 }
 
+/// The vault's balance rules with no accounts involved
+///
+/// Handlers do the CPIs and signer checks; every change to `vault.balance`
+/// goes through these functions, so they can be exercised on their
+/// own (see "Accounting Unit Tests" in the readme).
+pub mod accounting {
+    use super::*;
+
+    ///  FIX: Clean error instead of a panic when the balance would overflow
+    pub fn credit(balance: Lamports, amount: u64) -> Result<Lamports> {
+        balance
+            .checked_add(Lamports(amount))
+            .ok_or_else(|| error!(ErrorCode::Overflow))
+    }
+
+    /// Never lets a withdrawal exceed the recorded balance
+    pub fn debit(balance: Lamports, amount: u64) -> Result<Lamports> {
+        Ok(require_sufficient_balance!(balance, Lamports(amount)))
+    }
+
+    ///  FIX: Everything above the rent-exempt minimum, never the minimum
+    /// itself, so emptying the vault can't leave it to be garbage-collected
+    pub fn withdrawable(lamports: u64, rent_exempt: u64) -> Result<Lamports> {
+        Ok(require_sufficient_balance!(Lamports(lamports), Lamports(rent_exempt)))
+    }
+}
+
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
@@ -83,6 +196,8 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Anyone may deposit, so the depositor is not the owner: seeds come from
+/// the stored `vault.authority` (see PDA VALIDATION, "Mixing Seed Sources")
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
@@ -96,11 +211,14 @@ pub struct Deposit<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// The signer must be the owner, so seeds come from the signer, as in
+/// Initialize: a vault whose stored authority no longer matches its address
+/// fails here with ConstraintSeeds instead of resolving
 #[derive(Accounts)]
 pub struct SecureWithdraw<'info> {
     #[account(
         mut,
-        seeds = [b"vault", vault.authority.as_ref()],
+        seeds = [b"vault", authority.key().as_ref()],
         bump = vault.bump,
     )]
     pub vault: Account<'info, Vault>,
@@ -112,18 +230,65 @@ pub struct SecureWithdraw<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Only the vault's own authority may rewrite its ledger
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
     pub authority: Pubkey,
-    pub balance: u64,
+    pub balance: Lamports,
     pub bump: u8,
 }
 
+/// Emitted by every deposit. `vault` comes first and is marked `#[index]`:
+/// it sits at bytes 8..40 of the event data, so a client can filter a
+/// log stream by vault without decoding the rest
+#[event]
+pub struct Deposited {
+    #[index]
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    /// `vault.balance` after the deposit
+    pub balance: u64,
+}
+
+/// Emitted by `withdraw` and `withdraw_all`, laid out like `Deposited`
+#[event]
+pub struct Withdrawn {
+    #[index]
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// `vault.balance` after the withdrawal
+    pub balance: u64,
+}
+
+/// Emitted by `reconcile` when the ledger and the lamports disagree
+#[event]
+pub struct BalanceDrift {
+    pub vault: Pubkey,
+    pub tracked: u64,
+    pub actual: u64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Unauthorized: Authority mismatch")]
     Unauthorized,
     #[msg("Insufficient funds in vault")]
     InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    Overflow,
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-declare_id!("Vu1n11111111111111111111111111111111111111");
+declare_id!("Vu1n111111111111111111111111111111111111111");
 
 #[program]
 pub mod missing_signer_vulnerable {
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+declare_id!("SomeSpace5ecur22222222222222222222222222222");
+
+#[program]
+pub mod option_space_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, fee_bps: u16) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.pending_admin = None;
+        config.fee_bps = fee_bps;
+        config.bump = ctx.bumps.config;
+        msg!("Config initialized, admin: {}", config.admin);
+        Ok(())
+    }
+
+    /// Step 1 of a two-step handover: record who may accept
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        //  FIX: The account already has room for Some(..)
+        ctx.accounts.config.pending_admin = Some(new_admin);
+        msg!("Proposed admin: {}", new_admin);
+        Ok(())
+    }
+
+    /// Withdraw a proposal before it is accepted
+    pub fn cancel_proposal(ctx: Context<ProposeAdmin>) -> Result<()> {
+        ctx.accounts.config.pending_admin = None;
+        msg!("Admin proposal cancelled");
+        Ok(())
+    }
+
+    /// Step 2: the proposed key signs to take over
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.new_admin.key();
+        config.pending_admin = None;
+        msg!(" Admin handover accepted by {}", config.admin);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        //  FIX: Derived from the type, so every field is counted at its largest
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::NotPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+    pub new_admin: Signer<'info>,
+}
+
+///  SECURE: InitSpace counts `Option<Pubkey>` as 1 + 32, whatever it holds at init
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+}
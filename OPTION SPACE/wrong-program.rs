@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+declare_id!("SomeSpacevu1n111111111111111111111111111111");
+
+#[program]
+pub mod option_space_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, fee_bps: u16) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.pending_admin = None;
+        config.fee_bps = fee_bps;
+        config.bump = ctx.bumps.config;
+        msg!("Config initialized, admin: {}", config.admin);
+        Ok(())
+    }
+
+    /// Step 1 of a two-step handover: record who may accept
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        //  Some(..) needs 33 bytes; the account was sized for None's 1
+        ctx.accounts.config.pending_admin = Some(new_admin);
+        msg!("Proposed admin: {}", new_admin);
+        Ok(())
+    }
+
+    /// Step 2: the proposed key signs to take over
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.new_admin.key();
+        config.pending_admin = None;
+        msg!(" Admin handover accepted by {}", config.admin);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::NotPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+    pub new_admin: Signer<'info>,
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl Config {
+    ///  VULNERABLE: Hand-counted from a freshly initialized config!
+    /// admin (32) + pending_admin (1, it's None) + fee_bps (2) + bump (1)
+    pub const LEN: usize = 32 + 1 + 2 + 1;
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Move5ecur2222222222222222222222222222222222");
+
+#[program]
+pub mod ownership_move_secure {
+    use super::*;
+
+    ///  SECURE: The address comes from who created the vault and which of
+    /// their vaults it is. Neither ever changes, so ownership can
+    pub fn initialize(ctx: Context<Initialize>, vault_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.creator = ctx.accounts.authority.key();
+        vault.vault_id = vault_id;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault {} initialized for authority: {}", vault_id, vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Only the stored authority changes; the address doesn't
+    /// depend on it
+    pub fn transfer_ownership(ctx: Context<TransferOwnership>) -> Result<()> {
+        //  FIX: The new owner signs too, so the vault can't be handed to a
+        // key nobody holds - that would strand it just the same
+        let new_authority = ctx.accounts.new_authority.key();
+        ctx.accounts.vault.authority = new_authority;
+        msg!("Ownership transferred to {}", new_authority);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(vault_id: u64)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref(), &vault_id.to_le_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferOwnership<'info> {
+    //  FIX: Seeds from immutable fields; ownership is checked by has_one alone
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), &vault.vault_id.to_le_bytes()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    /// Seed: set once by `initialize`, never written again
+    pub creator: Pubkey,
+    /// Seed: lets one creator own several vaults
+    pub vault_id: u64,
+    /// Current owner; free to change because no seed depends on it
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+declare_id!("PdaDestvu1n11111111111111111111111111111111");
+
+#[program]
+pub mod pda_destination_vulnerable {
+    use super::*;
+
+    pub fn open_vault(ctx: Context<OpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!(" Opened vault for {}", vault.owner);
+        Ok(())
+    }
+
+    /// Anyone can pay into a user's vault, named by the user's key
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!(" Deposited {} lamports for {}", amount, vault.owner);
+        Ok(())
+    }
+
+    /// Sends `amount` to whichever account the owner names: their wallet,
+    /// an exchange, someone they're paying
+    ///
+    ///  VULNERABLE: The destination can be a vault of this program, this
+    /// one included. The lamports arrive, the `balance` that pays them out
+    /// doesn't, and no instruction can move them again
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault
+            .balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports to {}", amount, ctx.accounts.destination.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Only its key is used, to find the user's vault
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    //  BUG: Any writable account, including `vault` itself or another
+    // user's vault. Crediting an account needs no permission from its owner
+    /// CHECK: Only receives lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub owner: Pubkey,
+    /// Lamports the owner can withdraw; the rest of the account's lamports
+    /// are its rent
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as SplTransfer};
+
+declare_id!("TokSign5ecur2222222222222222222222222222222");
+
+#[program]
+pub mod pda_token_vault_secure {
+    use super::*;
+
+    /// Creates the vault state and its associated token account. The ATA's
+    /// authority is the vault PDA, so only this program can move its tokens
+    pub fn open_vault(ctx: Context<OpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.bump = ctx.bumps.vault;
+        msg!("Token vault opened for {}", vault.owner);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        // The depositor owns the source account, so their signature is enough
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.depositor_tokens.to_account_info(),
+                to: ctx.accounts.vault_tokens.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        );
+        token::transfer(cpi_ctx, amount)?;
+        msg!("Deposited {} tokens", amount);
+        Ok(())
+    }
+
+    ///  SECURE: The vault PDA signs for its own token account
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let owner = vault.owner;
+        let mint = vault.mint;
+
+        //  FIX: The same seeds the vault was created with, plus its stored bump
+        let seeds = &[
+            b"token_vault".as_ref(),
+            owner.as_ref(),
+            mint.as_ref(),
+            &[vault.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        //  FIX: new_with_signer - the runtime marks the PDA as a signer for this CPI
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.vault_tokens.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!(" Withdrew {} tokens", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TokenVault::INIT_SPACE,
+        seeds = [b"token_vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    ///  FIX: Owned by the vault PDA, not by the user
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Anyone may add tokens to a vault
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        seeds = [b"token_vault", vault.owner.as_ref(), vault.mint.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        associated_token::mint = vault.mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint, token::authority = depositor)]
+    pub depositor_tokens: Account<'info, TokenAccount>,
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        seeds = [b"token_vault", owner.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        associated_token::mint = vault.mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenVault {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// Needed to sign as the vault: the PDA has no private key
+    pub bump: u8,
+}
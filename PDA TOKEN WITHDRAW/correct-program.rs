@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as SplTransfer};
+
+declare_id!("PdaTok5ecur22222222222222222222222222222222");
+
+#[program]
+pub mod pda_token_withdraw_secure {
+    use super::*;
+
+    /// Funder locks `amount` tokens for `beneficiary` until `unlock_ts`
+    pub fn create_lockup(
+        ctx: Context<CreateLockup>,
+        beneficiary: Pubkey,
+        amount: u64,
+        unlock_ts: i64,
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.funder_tokens.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let lockup = &mut ctx.accounts.lockup;
+        lockup.beneficiary = beneficiary;
+        lockup.mint = ctx.accounts.mint.key();
+        lockup.amount = amount;
+        lockup.unlock_ts = unlock_ts;
+        lockup.bump = ctx.bumps.lockup;
+        msg!("Locked {} tokens for {} until {}", amount, beneficiary, unlock_ts);
+        Ok(())
+    }
+
+    ///  SECURE: Anyone may crank the release, but only to the beneficiary
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        let lockup = &ctx.accounts.lockup;
+        require!(
+            Clock::get()?.unix_timestamp >= lockup.unlock_ts,
+            ErrorCode::StillLocked
+        );
+        require!(lockup.amount > 0, ErrorCode::NothingToRelease);
+
+        let amount = lockup.amount;
+        let beneficiary = lockup.beneficiary;
+        let mint = lockup.mint;
+        let seeds = &[
+            b"lockup".as_ref(),
+            beneficiary.as_ref(),
+            mint.as_ref(),
+            &[lockup.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // destination.owner == beneficiary was enforced in the accounts struct
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.lockup.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        ctx.accounts.lockup.amount = 0;
+        msg!(" Released {} tokens", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateLockup<'info> {
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + Lockup::INIT_SPACE,
+        seeds = [b"lockup", beneficiary.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub lockup: Account<'info, Lockup>,
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"lockup_vault", lockup.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = lockup,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = funder)]
+    pub funder_tokens: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: keepers crank releases once they unlock
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(
+        mut,
+        seeds = [b"lockup", lockup.beneficiary.as_ref(), lockup.mint.as_ref()],
+        bump = lockup.bump,
+    )]
+    pub lockup: Account<'info, Lockup>,
+    #[account(mut, seeds = [b"lockup_vault", lockup.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    ///  FIX: Must be a token account the beneficiary owns, of the locked mint
+    #[account(
+        mut,
+        token::mint = lockup.mint,
+        constraint = destination.owner == lockup.beneficiary @ ErrorCode::WrongDestinationOwner,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Lockup {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Lockup has not reached its unlock time")]
+    StillLocked,
+    #[msg("Nothing left to release")]
+    NothingToRelease,
+    #[msg("Destination token account is not owned by the beneficiary")]
+    WrongDestinationOwner,
+}
@@ -1,5 +1,17 @@
 use anchor_lang::prelude::*;
 
+// SHARED UTILS/lamports.rs
+mod lamports;
+use lamports::Lamports;
+
+// SHARED UTILS/logging.rs
+mod logging;
+use logging::{audit_msg, log_msg};
+
+// SHARED UTILS/balance.rs
+mod balance;
+use balance::require_sufficient_balance;
+
 declare_id!("PDA5ecur22222222222222222222222222222222222");
 
 #[program]
@@ -9,16 +21,20 @@ pub mod pda_validation_secure {
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         vault.authority = ctx.accounts.authority.key();
-        vault.balance = 0;
+        vault.balance = Lamports::ZERO;
         vault.bump = ctx.bumps.vault;
-        msg!("Vault initialized");
+        log_msg!("Vault initialized");
         Ok(())
     }
 
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
-        vault.balance = vault.balance.checked_add(amount).unwrap();
-        msg!("Deposited: {}", amount);
+        //  FIX: Clean error instead of a panic when the balance would overflow
+        vault.balance = vault
+            .balance
+            .checked_add(Lamports(amount))
+            .ok_or(ErrorCode::Overflow)?;
+        log_msg!("Deposited: {}", amount);
         Ok(())
     }
 
@@ -28,8 +44,8 @@ pub mod pda_validation_secure {
         
         //  seeds constraint guarantees this is the CORRECT PDA
         // Attacker cannot pass fake vault
-        vault.balance = vault.balance.checked_sub(amount).unwrap();
-        msg!(" Withdrew from validated PDA: {}", amount);
+        vault.balance = require_sufficient_balance!(vault.balance, Lamports(amount));
+        audit_msg!(" Withdrew from validated PDA: {}", amount);
         Ok(())
     }
 }
@@ -49,6 +65,7 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Same seeds as Initialize; `bump = vault.bump` reuses the canonical bump init stored
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
@@ -78,6 +95,14 @@ pub struct SecureWithdraw<'info> {
 #[derive(InitSpace)]
 pub struct Vault {
     pub authority: Pubkey,
-    pub balance: u64,
+    pub balance: Lamports,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+}
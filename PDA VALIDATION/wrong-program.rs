@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-declare_id!("PDAvu1n11111111111111111111111111111111111");
+declare_id!("PDAvu1n111111111111111111111111111111111111");
 
 #[program]
 pub mod pda_validation_vulnerable {
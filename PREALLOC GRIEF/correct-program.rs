@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as SplTransfer};
+
+declare_id!("SquatGrief5ecur2222222222222222222222222222");
+
+#[program]
+pub mod prealloc_grief_secure {
+    use super::*;
+
+    /// Creates the vault state and its token account
+    ///
+    ///  SECURE: The token account is a PDA of this program, which only
+    /// this program can allocate
+    pub fn open_vault(ctx: Context<OpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.bump = ctx.bumps.vault;
+        vault.tokens_bump = ctx.bumps.vault_tokens;
+        msg!("Token vault opened for {}", vault.owner);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.depositor_tokens.to_account_info(),
+                to: ctx.accounts.vault_tokens.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        );
+        token::transfer(cpi_ctx, amount)?;
+        msg!("Deposited {} tokens", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let seeds = &[
+            b"token_vault".as_ref(),
+            vault.owner.as_ref(),
+            vault.mint.as_ref(),
+            &[vault.bump],
+        ];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.vault_tokens.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&seeds[..]],
+        );
+        token::transfer(cpi_ctx, amount)?;
+        msg!(" Withdrew {} tokens", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TokenVault::INIT_SPACE,
+        seeds = [b"token_vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    //  FIX: Allocating this address takes this program's signature. Lamports
+    // sent to it ahead of time don't block `init`: Anchor tops up, allocates
+    // and assigns instead of calling `create_account`
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Anyone may add tokens to a vault
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        seeds = [b"token_vault", vault.owner.as_ref(), vault.mint.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump = vault.tokens_bump,
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint, token::authority = depositor)]
+    pub depositor_tokens: Account<'info, TokenAccount>,
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        seeds = [b"token_vault", owner.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump = vault.tokens_bump,
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenVault {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub bump: u8,
+    /// Bump of the `vault_tokens` PDA
+    pub tokens_bump: u8,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+declare_id!("ReentHook1111111111111111111111111111111111");
+
+/// A "hook" that tries to call back into the pool program's `withdraw`.
+/// Used by the tests to show the runtime blocks A -> B -> A re-entry, and
+/// why the real risk is a hook that points back at the pool itself
+#[program]
+pub mod reentrancy_hook {
+    use super::*;
+
+    pub fn hook(ctx: Context<Hook>, amount: u64) -> Result<()> {
+        // Anchor instruction data: sha256("global:withdraw")[..8] ++ amount ++ empty hook_data
+        let mut data = hash(b"global:withdraw").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+
+        let ix = Instruction {
+            program_id: ctx.accounts.pool_program.key(),
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.key(),
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data,
+        };
+
+        //  Rejected by the runtime: the pool program is already on the stack
+        invoke(&ix, ctx.remaining_accounts)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Hook<'info> {
+    /// CHECK: Vulnerable or secure pool program
+    pub pool_program: AccountInfo<'info>,
+}
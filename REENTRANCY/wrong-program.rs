@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+declare_id!("Reentvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod reentrancy_vulnerable {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        ctx.accounts.pool.bump = ctx.bumps.pool;
+        msg!("Pool initialized");
+        Ok(())
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Pays out, then calls the owner's hook program (e.g. to auto-stake
+    /// the withdrawal) with `hook_data` and the remaining accounts
+    ///
+    ///  VULNERABLE: The balance is debited after the hook runs!
+    /// A hook that calls `withdraw` again sees the old balance
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64, hook_data: Vec<u8>) -> Result<()> {
+        require!(
+            ctx.accounts.position.balance >= amount,
+            ErrorCode::InsufficientFunds
+        );
+
+        ctx.accounts.pool.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        if !hook_data.is_empty() {
+            let ix = Instruction {
+                program_id: ctx.accounts.hook_program.key(),
+                accounts: ctx
+                    .remaining_accounts
+                    .iter()
+                    .map(|a| AccountMeta {
+                        pubkey: a.key(),
+                        is_signer: a.is_signer,
+                        is_writable: a.is_writable,
+                    })
+                    .collect(),
+                data: hook_data,
+            };
+            //  Control leaves the program with `position` not yet updated
+            invoke(&ix, ctx.remaining_accounts)?;
+        }
+
+        //  Runs after any nested withdraw, on a stale copy of `position`
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner,
+    )]
+    pub position: Account<'info, Position>,
+    pub owner: Signer<'info>,
+    /// CHECK: Where the withdrawn lamports go
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    /// CHECK: Owner-chosen program; only invoked when hook_data is non-empty
+    pub hook_program: AccountInfo<'info>,
+}
+
+/// Holds every depositor's lamports
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in position")]
+    InsufficientFunds,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Rent5ecur2222222222222222222222222222222222");
+
+#[program]
+pub mod rent_payer_secure {
+    use super::*;
+
+    pub fn create_profile(ctx: Context<CreateProfile>, handle: String) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.authority = ctx.accounts.authority.key();
+        profile.handle = handle;
+        profile.bump = ctx.bumps.profile;
+        msg!("Profile created for {}", profile.authority);
+        Ok(())
+    }
+
+    pub fn close_profile(_ctx: Context<CloseProfile>) -> Result<()> {
+        msg!("Profile closed");
+        Ok(())
+    }
+}
+
+///  SECURE: The account's owner funds its rent
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        //  FIX: payer is the authority, not a separate account
+        payer = authority,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [b"profile", authority.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+///  SECURE: Refund returns to the account that paid
+#[derive(Accounts)]
+pub struct CloseProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", authority.key().as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        close = authority,
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub authority: Pubkey,
+    #[max_len(32)]
+    pub handle: String,
+    pub bump: u8,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Rentvu1n11111111111111111111111111111111111");
+
+#[program]
+pub mod rent_payer_vulnerable {
+    use super::*;
+
+    pub fn create_profile(ctx: Context<CreateProfile>, handle: String) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.authority = ctx.accounts.authority.key();
+        profile.handle = handle;
+        profile.bump = ctx.bumps.profile;
+        msg!("Profile created for {}", profile.authority);
+        Ok(())
+    }
+
+    pub fn close_profile(_ctx: Context<CloseProfile>) -> Result<()> {
+        msg!("Profile closed");
+        Ok(())
+    }
+}
+
+///  VULNERABLE: Rent comes from whichever account is passed as `payer`
+/// Any other signer in the transaction - a relayer, a fee sponsor, a
+/// co-signing dApp wallet - can be made to fund the attacker's account
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [b"profile", authority.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+    /// CHECK: Never compared to `authority` - anyone who happens to sign
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+///  VULNERABLE: Rent refund goes to the authority, not to whoever paid it
+#[derive(Accounts)]
+pub struct CloseProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", authority.key().as_ref()],
+        bump = profile.bump,
+        has_one = authority,
+        close = authority,
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub authority: Pubkey,
+    #[max_len(32)]
+    pub handle: String,
+    pub bump: u8,
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    instruction::Instruction,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+declare_id!("Rep1vu1n11111111111111111111111111111111111");
+
+#[program]
+pub mod replay_protection_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Authorization has no nonce!
+    /// The same signed message can be submitted again and again
+    pub fn relayed_withdraw(ctx: Context<RelayedWithdraw>, amount: u64) -> Result<()> {
+        //  Message only binds vault, recipient and amount
+        let message = withdraw_message(
+            &ctx.accounts.vault.key(),
+            &ctx.accounts.recipient.key(),
+            amount,
+        );
+
+        // The signature itself IS verified...
+        let ix_sysvar = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&ix_sysvar)?;
+        require!(current_index > 0, ErrorCode::MissingAuthorization);
+        let ed25519_ix = load_instruction_at_checked(current_index as usize - 1, &ix_sysvar)?;
+        verify_ed25519_ix(&ed25519_ix, &ctx.accounts.vault.authority, &message)?;
+
+        //  ...but nothing records that it was already used
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+
+        ctx.accounts.vault.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        msg!(" Relayed withdrawal of {} lamports (replayable!)", amount);
+        Ok(())
+    }
+}
+
+fn withdraw_message(vault: &Pubkey, recipient: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(72);
+    message.extend_from_slice(vault.as_ref());
+    message.extend_from_slice(recipient.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message
+}
+
+/// Checks that `ix` is a single-signature Ed25519 program instruction
+/// signed by `signer` over exactly `message`
+fn verify_ed25519_ix(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidAuthorization);
+    require!(ix.accounts.is_empty(), ErrorCode::InvalidAuthorization);
+
+    // 2 byte header + one 14 byte Ed25519SignatureOffsets entry
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidAuthorization);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix = read_u16(14);
+
+    // Offsets must point into this instruction, not some other one
+    require!(
+        signature_ix == u16::MAX && public_key_ix == u16::MAX && message_ix == u16::MAX,
+        ErrorCode::InvalidAuthorization
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidAuthorization)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidAuthorization)?;
+
+    require!(public_key == signer.as_ref(), ErrorCode::InvalidAuthorization);
+    require!(signed_message == message, ErrorCode::InvalidAuthorization);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RelayedWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Bound by the signed message
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    /// Anyone may relay; the vault authority signs off-chain
+    pub relayer: Signer<'info>,
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Missing Ed25519 authorization instruction")]
+    MissingAuthorization,
+    #[msg("Invalid Ed25519 authorization")]
+    InvalidAuthorization,
+}
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+declare_id!("Auct5ecur2222222222222222222222222222222222");
+
+#[program]
+pub mod sealed_auction_secure {
+    use super::*;
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        auction_id: u64,
+        commit_duration: i64,
+        reveal_duration: i64,
+    ) -> Result<()> {
+        require!(commit_duration > 0 && reveal_duration > 0, ErrorCode::InvalidDuration);
+
+        let now = Clock::get()?.unix_timestamp;
+        let commit_end_ts = now
+            .checked_add(commit_duration)
+            .ok_or(ErrorCode::InvalidDuration)?;
+        let reveal_end_ts = commit_end_ts
+            .checked_add(reveal_duration)
+            .ok_or(ErrorCode::InvalidDuration)?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.seller = ctx.accounts.seller.key();
+        auction.auction_id = auction_id;
+        auction.highest_bid = 0;
+        auction.highest_bidder = Pubkey::default();
+        auction.commit_end_ts = commit_end_ts;
+        auction.reveal_end_ts = reveal_end_ts;
+        auction.phase = Phase::Commit;
+        auction.bump = ctx.bumps.auction;
+        msg!("Auction {} created", auction_id);
+        Ok(())
+    }
+
+    ///  SECURE: Only a hash of (amount, nonce, bidder) is submitted
+    /// Observers learn nothing about the bid amount
+    pub fn commit_bid(ctx: Context<CommitBid>, commitment: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &mut ctx.accounts.auction;
+        advance_phase(auction, now);
+
+        require!(auction.phase == Phase::Commit, ErrorCode::WrongPhase);
+
+        let bid = &mut ctx.accounts.bid;
+        bid.auction = auction.key();
+        bid.bidder = ctx.accounts.bidder.key();
+        bid.commitment = commitment;
+        bid.revealed = false;
+        bid.bump = ctx.bumps.bid;
+        msg!(" Sealed bid committed");
+        Ok(())
+    }
+
+    ///  SECURE: Bid only counts if it matches the earlier commitment
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, nonce: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &mut ctx.accounts.auction;
+        advance_phase(auction, now);
+
+        require!(auction.phase == Phase::Reveal, ErrorCode::WrongPhase);
+
+        let bid = &mut ctx.accounts.bid;
+        require!(!bid.revealed, ErrorCode::AlreadyRevealed);
+
+        //  Bidder key is part of the preimage, so a commitment
+        // copied from another bidder can never be revealed
+        let expected = hashv(&[
+            &amount.to_le_bytes(),
+            &nonce,
+            ctx.accounts.bidder.key().as_ref(),
+        ]);
+        require!(expected.to_bytes() == bid.commitment, ErrorCode::CommitmentMismatch);
+
+        bid.revealed = true;
+
+        if amount > auction.highest_bid {
+            auction.highest_bid = amount;
+            auction.highest_bidder = ctx.accounts.bidder.key();
+        }
+        msg!(" Bid revealed and verified");
+        Ok(())
+    }
+
+    pub fn end_auction(ctx: Context<EndAuction>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &mut ctx.accounts.auction;
+        advance_phase(auction, now);
+
+        require!(auction.phase == Phase::Ended, ErrorCode::WrongPhase);
+        msg!("Auction won by {} with {}", auction.highest_bidder, auction.highest_bid);
+        Ok(())
+    }
+}
+
+///  Phases only move forward, driven by the clock rather than by any caller
+fn advance_phase(auction: &mut Auction, now: i64) {
+    if now >= auction.reveal_end_ts {
+        auction.phase = Phase::Ended;
+    } else if now >= auction.commit_end_ts {
+        auction.phase = Phase::Reveal;
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CreateAuction<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [b"auction", seller.key().as_ref(), &auction_id.to_le_bytes()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.seller.as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+    ///  One sealed bid per bidder per auction
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.seller.as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        mut,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = auction,
+        has_one = bidder,
+    )]
+    pub bid: Account<'info, Bid>,
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EndAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.seller.as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller,
+    )]
+    pub auction: Account<'info, Auction>,
+    pub seller: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub seller: Pubkey,
+    pub auction_id: u64,
+    pub highest_bid: u64,
+    pub highest_bidder: Pubkey,
+    pub commit_end_ts: i64,
+    pub reveal_end_ts: i64,
+    pub phase: Phase,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub commitment: [u8; 32],
+    pub revealed: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Phase {
+    Commit,
+    Reveal,
+    Ended,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Commit and reveal durations must be positive")]
+    InvalidDuration,
+    #[msg("Instruction not allowed in the current auction phase")]
+    WrongPhase,
+    #[msg("Bid has already been revealed")]
+    AlreadyRevealed,
+    #[msg("Revealed bid does not match commitment")]
+    CommitmentMismatch,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Auctvu1n11111111111111111111111111111111111");
+
+#[program]
+pub mod sealed_auction_vulnerable {
+    use super::*;
+
+    pub fn create_auction(ctx: Context<CreateAuction>, auction_id: u64, duration: i64) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        auction.seller = ctx.accounts.seller.key();
+        auction.auction_id = auction_id;
+        auction.highest_bid = 0;
+        auction.highest_bidder = Pubkey::default();
+        auction.end_ts = Clock::get()?.unix_timestamp + duration;
+        auction.phase = Phase::Open;
+        auction.bump = ctx.bumps.auction;
+        msg!("Auction {} created", auction_id);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Bid amount is submitted in plaintext!
+    /// Every bid is visible in the mempool and on-chain before the auction ends
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(auction.phase == Phase::Open, ErrorCode::AuctionClosed);
+        require!(now < auction.end_ts, ErrorCode::AuctionClosed);
+
+        //  A watcher sees `amount` and submits `amount + 1` right behind it
+        require!(amount > auction.highest_bid, ErrorCode::BidTooLow);
+
+        auction.highest_bid = amount;
+        auction.highest_bidder = ctx.accounts.bidder.key();
+        msg!(" Plaintext bid of {} from {}", amount, auction.highest_bidder);
+        Ok(())
+    }
+
+    pub fn end_auction(ctx: Context<EndAuction>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        require!(
+            Clock::get()?.unix_timestamp >= auction.end_ts,
+            ErrorCode::AuctionStillOpen
+        );
+
+        auction.phase = Phase::Ended;
+        msg!("Auction won by {} with {}", auction.highest_bidder, auction.highest_bid);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CreateAuction<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [b"auction", seller.key().as_ref(), &auction_id.to_le_bytes()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.seller.as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EndAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.seller.as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller,
+    )]
+    pub auction: Account<'info, Auction>,
+    pub seller: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub seller: Pubkey,
+    pub auction_id: u64,
+    pub highest_bid: u64,
+    pub highest_bidder: Pubkey,
+    pub end_ts: i64,
+    pub phase: Phase,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Phase {
+    Open,
+    Ended,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Auction is closed")]
+    AuctionClosed,
+    #[msg("Auction is still open")]
+    AuctionStillOpen,
+    #[msg("Bid must exceed the current highest bid")]
+    BidTooLow,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+declare_id!("AttackHarness111111111111111111111111111111");
+
+/// A configurable adversarial callee for exploit tests
+///
+/// Deploy it next to the program under test and hand it to whatever CPI
+/// that program makes into a caller-chosen (or unchecked) program. What it
+/// does when called is picked per call with a `Behavior` in the
+/// instruction data, so one deployment covers every scenario.
+#[program]
+pub mod attacker_harness {
+    use super::*;
+
+    /// Runs `behavior` against the remaining accounts, in the order it documents
+    pub fn attack(ctx: Context<Attack>, behavior: Behavior) -> Result<()> {
+        let accounts = ctx.remaining_accounts;
+
+        match behavior {
+            Behavior::Log => log_accounts(accounts),
+            Behavior::Fail => err!(AttackerError::ConfiguredToFail),
+            Behavior::Steal { lamports } => {
+                let [from, to, system_program, ..] = accounts else {
+                    return err!(AttackerError::MissingAccounts);
+                };
+                //  Works whenever the caller forwarded `from` as a signer
+                let cpi_context = CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: from.clone(),
+                        to: to.clone(),
+                    },
+                );
+                anchor_lang::system_program::transfer(cpi_context, lamports)?;
+                msg!("Attacker: moved {} lamports out of {}", lamports, from.key());
+                Ok(())
+            }
+            Behavior::Reenter { data } => {
+                let [program, rest @ ..] = accounts else {
+                    return err!(AttackerError::MissingAccounts);
+                };
+                let ix = Instruction {
+                    program_id: program.key(),
+                    accounts: rest
+                        .iter()
+                        .map(|a| AccountMeta {
+                            pubkey: a.key(),
+                            is_signer: a.is_signer,
+                            is_writable: a.is_writable,
+                        })
+                        .collect(),
+                    data,
+                };
+                msg!("Attacker: calling back into {}", program.key());
+                invoke(&ix, rest)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Any instruction data that isn't `attack` - a System Program transfer,
+    /// another program's Anchor instruction - lands here. Reports success
+    /// without doing anything: the callee that says "done" and isn't
+    pub fn fallback<'info>(
+        _program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        msg!("Attacker: accepted {} bytes of instruction data, did nothing", data.len());
+        log_accounts(accounts)
+    }
+}
+
+/// What the harness does when called through `attack`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    /// Succeed and log every account it was handed, with its signer and
+    /// writable flags - shows exactly what privileges the caller forwarded
+    Log,
+    /// Return an error, for testing that the caller rolls back cleanly
+    Fail,
+    /// Transfer `lamports` with a forwarded signature.
+    /// Accounts: `[from (signer, writable), to (writable), system_program]`
+    Steal { lamports: u64 },
+    /// Call a program with `data`, passing the rest of the accounts on with
+    /// their flags unchanged. Accounts: `[program, ...accounts]`
+    Reenter { data: Vec<u8> },
+}
+
+fn log_accounts(accounts: &[AccountInfo]) -> Result<()> {
+    for (i, account) in accounts.iter().enumerate() {
+        msg!(
+            "Attacker: account {} {} signer={} writable={}",
+            i,
+            account.key(),
+            account.is_signer,
+            account.is_writable
+        );
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Attack {}
+
+#[error_code]
+pub enum AttackerError {
+    #[msg("Attacker configured to fail")]
+    ConfiguredToFail,
+    #[msg("Not enough accounts for this behavior")]
+    MissingAccounts,
+}
@@ -0,0 +1,48 @@
+//! `require_sufficient_balance!`: the balance check and the subtraction in one step
+//!
+//! Withdrawals kept spelling the same two steps: `require!(balance >= amount)`
+//! and then a subtraction, sometimes checked, sometimes `.unwrap()`ed, once
+//! or twice plain `-`. With two steps, a later edit can keep the check and
+//! lose the checked math, or the other way round. This macro is a single
+//! step. It subtracts with `checked_sub`, evaluates to the remaining
+//! balance, and on underflow returns `InsufficientFunds` from the
+//! enclosing function.
+//!
+//! Expects the including program to define `ErrorCode::InsufficientFunds`
+//! at its crate root, or to name its own error as a third argument.
+
+/// `balance - amount`, or an early `return Err(InsufficientFunds)`
+///
+/// ```ignore
+/// vault.balance = require_sufficient_balance!(vault.balance, Lamports(amount));
+/// let left = require_sufficient_balance!(escrow.amount, fee, ErrorCode::FeeTooHigh);
+/// ```
+///
+/// Works for any type with `checked_sub(self, rhs) -> Option<Self>`: `u64`,
+/// the other integers, and `Lamports`. Each argument is evaluated exactly
+/// once, the binding inside is hygienic, and the error goes through
+/// `error!` so the log names the file and line of the call. The `return`
+/// leaves the function the macro is written in, so the function must
+/// return an Anchor `Result` or `ProgramResult`.
+macro_rules! require_sufficient_balance {
+    ($balance:expr, $amount:expr $(,)?) => {
+        match $balance.checked_sub($amount) {
+            ::core::option::Option::Some(remaining) => remaining,
+            ::core::option::Option::None => {
+                return ::core::result::Result::Err(
+                    anchor_lang::prelude::error!(crate::ErrorCode::InsufficientFunds).into(),
+                );
+            }
+        }
+    };
+    ($balance:expr, $amount:expr, $error:expr $(,)?) => {
+        match $balance.checked_sub($amount) {
+            ::core::option::Option::Some(remaining) => remaining,
+            ::core::option::Option::None => {
+                return ::core::result::Result::Err(anchor_lang::prelude::error!($error).into());
+            }
+        }
+    };
+}
+
+pub(crate) use require_sufficient_balance;
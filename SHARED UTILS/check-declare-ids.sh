@@ -0,0 +1,66 @@
+#!/usr/bin/env bash
+# Fails if two programs in the collection share a declare_id!, if an id
+# isn't a valid 32-byte public key, or if a program has none. Every example is deployed into one local validator by
+# the test harness, so ids must be unique across all folders, not just
+# within a wrong/correct pair.
+#
+# Usage (from anywhere in the repo):
+#   bash "SHARED UTILS/check-declare-ids.sh"
+
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+status=0
+
+# <id>\t<file> for every declare_id! in a .rs file
+ids=$(grep -rHo --include='*.rs' 'declare_id!("[^"]*")' . \
+    | sed -E 's|^\./(.*):declare_id!\("([^"]*)"\)$|\2\t\1|' \
+    | sort)
+
+dups=$(cut -f1 <<<"$ids" | uniq -d)
+for id in $dups; do
+    echo "Duplicate program id $id:"
+    awk -F'\t' -v id="$id" '$1 == id { print "    " $2 }' <<<"$ids"
+    status=1
+done
+
+# declare_id! only compiles for base58 that decodes to exactly 32 bytes.
+# A placeholder padded with one '1' too many or too few decodes to 31 or
+# 33, and a 0, O, I or l isn't base58 at all
+bad=$(cut -f1 <<<"$ids" | sort -u | python3 -c '
+import sys
+ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+for id in sys.stdin.read().split():
+    if any(c not in ALPHABET for c in id):
+        print(id, "is not base58")
+        continue
+    n = 0
+    for c in id:
+        n = n * 58 + ALPHABET.index(c)
+    size = len(id) - len(id.lstrip("1")) + (n.bit_length() + 7) // 8
+    if size != 32:
+        print(id, "decodes to", size, "bytes, not 32")
+')
+while IFS= read -r line; do
+    [ -n "$line" ] || continue
+    id=${line%% *}
+    echo "Invalid program id $line:"
+    awk -F'\t' -v id="$id" '$1 == id { print "    " $2 }' <<<"$ids"
+    status=1
+done <<<"$bad"
+
+# A #[program] module with no id would fall back to whatever the build
+# environment supplies, which hides collisions instead of catching them
+while IFS= read -r -d '' file; do
+    if grep -q '#\[program\]' "$file" && ! grep -q 'declare_id!' "$file"; then
+        echo "Missing declare_id!: ${file#./}"
+        status=1
+    fi
+done < <(find . -name '*.rs' -not -path './.git/*' -print0)
+
+if [ "$status" -eq 0 ]; then
+    echo "$(wc -l <<<"$ids") program ids, all unique and 32 bytes"
+fi
+
+exit "$status"
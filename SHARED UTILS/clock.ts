@@ -0,0 +1,22 @@
+import { Clock, ProgramTestContext } from "solana-bankrun";
+
+/** The bank's current `unix_timestamp`, as `Clock::get()` would read it */
+export const now = async (context: ProgramTestContext): Promise<bigint> =>
+  (await context.banksClient.getClock()).unixTimestamp;
+
+/**
+ * Sets the bank's clock to `unixTimestamp`. Slot and epoch are left as
+ * they are, so only the program's time checks see the jump - blockhashes
+ * and rent don't.
+ */
+export const warpTo = async (context: ProgramTestContext, unixTimestamp: bigint): Promise<void> => {
+  const clock = await context.banksClient.getClock();
+  context.setClock(new Clock(
+    clock.slot, clock.epochStartTimestamp, clock.epoch,
+    clock.leaderScheduleEpoch, unixTimestamp,
+  ));
+};
+
+/** Moves the clock `seconds` forward, or back if `seconds` is negative */
+export const warpBy = async (context: ProgramTestContext, seconds: bigint): Promise<void> =>
+  warpTo(context, (await now(context)) + seconds);
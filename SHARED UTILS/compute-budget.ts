@@ -0,0 +1,170 @@
+import * as fs from "fs";
+import { Connection, PublicKey } from "@solana/web3.js";
+
+/** `[program]` sections of `instruction = units` lines */
+export type Budgets = Record<string, Record<string, number>>;
+
+/**
+ * Parses the subset of TOML `budgets.toml` uses: `[section]` headers,
+ * `name = 12_345` integer lines and `#` comments. Anything else throws, so
+ * a typo in the file fails the run instead of silently dropping a budget.
+ */
+export const parseBudgets = (text: string): Budgets => {
+  const budgets: Budgets = {};
+  let section: string | undefined;
+
+  text.split("\n").forEach((raw, i) => {
+    const line = raw.replace(/#.*/, "").trim();
+    if (!line) return;
+
+    const header = line.match(/^\[([A-Za-z0-9_]+)\]$/);
+    if (header) {
+      section = header[1];
+      budgets[section] ??= {};
+      return;
+    }
+
+    const entry = line.match(/^([A-Za-z0-9_]+)\s*=\s*([0-9_]+)$/);
+    if (!entry || !section) {
+      throw new Error(`budgets.toml line ${i + 1}: expected [section] or name = units, got "${raw}"`);
+    }
+    budgets[section][entry[1]] = Number(entry[2].replace(/_/g, ""));
+  });
+  return budgets;
+};
+
+export const loadBudgets = (path = "budgets.toml"): Budgets =>
+  parseBudgets(fs.readFileSync(path, "utf8"));
+
+/**
+ * Compute units of each top-level invocation of `programId`, in order,
+ * from a transaction's logs. CPIs the program makes are included in its
+ * own figure; `ComputeBudget` and other programs in the same transaction
+ * are not.
+ */
+export const topLevelUnits = (logs: string[], programId: PublicKey): number[] => {
+  const id = programId.toBase58();
+  const units: number[] = [];
+  let depth = 0;
+
+  for (const line of logs) {
+    const invoke = line.match(/^Program (\w+) invoke \[(\d+)\]$/);
+    if (invoke) {
+      depth = Number(invoke[2]);
+      continue;
+    }
+    const consumed = line.match(/^Program (\w+) consumed (\d+) of \d+ compute units$/);
+    if (consumed) {
+      if (depth === 1 && consumed[1] === id) units.push(Number(consumed[2]));
+      continue;
+    }
+    if (/^Program \w+ (success|failed)/.test(line)) depth -= 1;
+  }
+  return units;
+};
+
+/** Logs of a confirmed transaction, for use with `ComputeRecorder.record` */
+export const logsOf = async (connection: Connection, signature: string): Promise<string[]> => {
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return tx?.meta?.logMessages ?? [];
+};
+
+/**
+ * Collects the most expensive run of each instruction and checks it
+ * against `budgets[section]`:
+ *
+ *   const recorder = new ComputeRecorder(loadBudgets(), "missing_signer_secure");
+ *   recorder.record("deposit", await logsOf(connection, sig), program.programId);
+ *   ...
+ *   recorder.assertWithinBudgets();
+ *
+ * An instruction with no budget fails too: a new instruction has to be
+ * given one before it can land.
+ */
+export class ComputeRecorder {
+  private readonly worst = new Map<string, number>();
+
+  constructor(
+    private readonly budgets: Budgets,
+    private readonly section: string,
+  ) {}
+
+  /** Records every top-level call to `programId` in `logs` under `instruction` */
+  record(instruction: string, logs: string[], programId: PublicKey): number[] {
+    const units = topLevelUnits(logs, programId);
+    if (units.length === 0) {
+      throw new Error(`No compute figure for ${instruction} in the logs - wrong program id?`);
+    }
+    this.worst.set(instruction, Math.max(this.worst.get(instruction) ?? 0, ...units));
+    return units;
+  }
+
+  /** One line per recorded instruction that is over or missing its budget */
+  violations(): string[] {
+    const budgets = this.budgets[this.section] ?? {};
+    return [...this.worst].flatMap(([name, used]) => {
+      const budget = budgets[name];
+      if (budget === undefined) return [`${this.section}.${name}: ${used} CU, no budget in budgets.toml`];
+      if (used > budget) return [`${this.section}.${name}: ${used} CU, budget ${budget} (+${used - budget})`];
+      return [];
+    });
+  }
+
+  assertWithinBudgets(): void {
+    const violations = this.violations();
+    if (violations.length > 0) {
+      throw new Error(`Compute budget exceeded:\n  ${violations.join("\n  ")}\n\n` +
+        `If the increase is intended, update budgets.toml:\n\n${this.suggest()}`);
+    }
+  }
+
+  /**
+   * A `[section]` block with each recorded figure plus 20% headroom,
+   * rounded up to 500 CU - the starting point for a new or raised budget
+   */
+  suggest(): string {
+    const lines = [...this.worst]
+      .sort(([a], [b]) => a.localeCompare(b))
+      .map(([name, used]) => {
+        const budget = Math.ceil((used * 1.2) / 500) * 500;
+        return `${name} = ${budget.toLocaleString("en-US").replace(/,/g, "_")}  # measured ${used}`;
+      });
+    return [`[${this.section}]`, ...lines].join("\n");
+  }
+}
+
+/** One run of a sweep: compute used, and the error if the transaction failed */
+export type Measurement = { units: number; error: string | null };
+
+/**
+ * Runs `measure` once per size, in order, and keeps every result, failures
+ * included. For plotting compute against an input that should, or
+ * shouldn't, be bounded: remaining accounts, rounds, list length.
+ */
+export const sweep = async (
+  sizes: number[],
+  measure: (size: number) => Promise<Measurement>,
+): Promise<Map<number, Measurement>> => {
+  const results = new Map<number, Measurement>();
+  for (const size of sizes) results.set(size, await measure(size));
+  return results;
+};
+
+/**
+ * A plain-text table with one row per size and one column per sweep.
+ * Failed runs read `failed (N CU)`, so the row where a version stops
+ * fitting stands out.
+ */
+export const formatSweep = (label: string, columns: Record<string, Map<number, Measurement>>): string => {
+  const names = Object.keys(columns);
+  const sizes = [...new Set(names.flatMap((name) => [...columns[name].keys()]))].sort((a, b) => a - b);
+  const cell = (m?: Measurement) =>
+    !m ? "-" : m.error ? `failed (${m.units.toLocaleString("en-US")} CU)` : m.units.toLocaleString("en-US");
+
+  const rows = [[label, ...names], ...sizes.map((size) => [String(size), ...names.map((n) => cell(columns[n].get(size)))])];
+  const widths = rows[0].map((_, i) => Math.max(...rows.map((row) => row[i].length)));
+  return rows.map((row) => row.map((text, i) => text.padStart(widths[i])).join("   ")).join("\n");
+};
@@ -0,0 +1,66 @@
+//! Constant-product (`x * y = k`) pool math with no accounts in sight
+//!
+//! Everything here is a plain function of integers, so the same code the
+//! program runs can be driven from a simulation, a fuzzer or another pool
+//! example without building a `Context`. Products are taken in `u128`,
+//! outputs are floored, and every quote is checked against `k` before it
+//! is returned.
+
+/// Why a quote was refused; the including program maps these onto its
+/// own `ErrorCode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveError {
+    Overflow,
+    ZeroOutput,
+    EmptyReserve,
+    InvariantViolated,
+}
+
+/// Result of swapping `amount_in` into a pool, before anything is stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub amount_out: u64,
+    pub new_reserve_in: u64,
+    pub new_reserve_out: u64,
+}
+
+/// The invariant, widened so any pair of `u64` reserves fits
+pub fn k(reserve_a: u64, reserve_b: u64) -> u128 {
+    (reserve_a as u128) * (reserve_b as u128)
+}
+
+/// Prices a swap of `amount_in` against `reserve_in` / `reserve_out`
+///
+/// Rounds in the pool's favour, refuses swaps that would return nothing
+/// or empty the output side, and guarantees
+/// `k(new_reserve_in, new_reserve_out) >= k(reserve_in, reserve_out)`.
+pub fn quote_swap(reserve_in: u64, reserve_out: u64, amount_in: u64) -> Result<SwapQuote, CurveError> {
+    let k_before = k(reserve_in, reserve_out);
+    let numerator = (reserve_out as u128) * (amount_in as u128);
+    let denominator = (reserve_in as u128) + (amount_in as u128);
+    if denominator == 0 {
+        return Err(CurveError::EmptyReserve);
+    }
+    //  Floor division - the trader pays the rounding, never the pool
+    let amount_out = u64::try_from(numerator / denominator).map_err(|_| CurveError::Overflow)?;
+    if amount_out == 0 {
+        return Err(CurveError::ZeroOutput);
+    }
+
+    let new_reserve_in = reserve_in.checked_add(amount_in).ok_or(CurveError::Overflow)?;
+    let new_reserve_out = reserve_out
+        .checked_sub(amount_out)
+        .filter(|reserve| *reserve > 0)
+        .ok_or(CurveError::EmptyReserve)?;
+
+    //  The invariant itself, checked on the values about to be stored
+    if k(new_reserve_in, new_reserve_out) < k_before {
+        return Err(CurveError::InvariantViolated);
+    }
+
+    Ok(SwapQuote {
+        amount_out,
+        new_reserve_in,
+        new_reserve_out,
+    })
+}
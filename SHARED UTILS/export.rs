@@ -0,0 +1,88 @@
+//! Read-only snapshots of a program's accounts, for off-chain auditing
+//!
+//! An auditor rebuilding a program's state fetches its accounts in batches
+//! an RPC node may serve from different slots, then decodes them with
+//! whatever layout their tooling assumes. `snapshot` copies the accounts a
+//! transaction names into one canonical blob, read in a single slot by the
+//! program that owns them:
+//!
+//! ```text
+//! [format: u8][slot: u64][count: u32], then per account, in address order:
+//! [address: 32][len: u32][data: len bytes, discriminator included]
+//! ```
+//!
+//! That is the Borsh encoding of `Snapshot`, so it is in the IDL and a
+//! client reads it like any other return value. Account data is copied
+//! byte for byte, never re-serialized: its discriminator tells the decoder
+//! which IDL type to read it as (see `idl_decoder.rs`).
+//!
+//! Nothing is written. Simulate the instruction rather than sending it.
+//! Return data is capped at 1,024 bytes, so large sets are exported in pages.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+
+/// Changes whenever the layout below does
+pub const SNAPSHOT_FORMAT: u8 = 1;
+
+/// Format, slot and account count
+const SNAPSHOT_HEADER_LEN: usize = 1 + 8 + 4;
+
+/// Address and data length, ahead of each account's data
+const ENTRY_HEADER_LEN: usize = 32 + 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Snapshot {
+    pub format: u8,
+    /// Slot every account was read in
+    pub slot: u64,
+    /// Sorted by address, no duplicates
+    pub accounts: Vec<ExportedAccount>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExportedAccount {
+    pub address: Pubkey,
+    /// The account's data as stored, discriminator included
+    pub data: Vec<u8>,
+}
+
+/// Snapshots `accounts`, each of which must be owned by `program_id`. The
+/// order they are passed in doesn't change the result
+pub fn snapshot(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<Snapshot> {
+    require!(!accounts.is_empty(), ExportError::NoAccounts);
+
+    let mut sorted: Vec<&AccountInfo> = accounts.iter().collect();
+    sorted.sort_by(|a, b| a.key.cmp(b.key));
+
+    let mut exported: Vec<ExportedAccount> = Vec::with_capacity(sorted.len());
+    let mut size = SNAPSHOT_HEADER_LEN;
+    for info in sorted {
+        // Another program's account isn't this program's state to vouch for
+        require_keys_eq!(*info.owner, *program_id, ExportError::WrongOwner);
+        if let Some(last) = exported.last() {
+            require_keys_neq!(last.address, info.key(), ExportError::DuplicateAccount);
+        }
+
+        let data = info.try_borrow_data()?;
+        size += ENTRY_HEADER_LEN + data.len();
+        require!(size <= MAX_RETURN_DATA, ExportError::TooLarge);
+        exported.push(ExportedAccount { address: info.key(), data: data.to_vec() });
+    }
+
+    Ok(Snapshot { format: SNAPSHOT_FORMAT, slot: Clock::get()?.slot, accounts: exported })
+}
+
+/// Offset keeps these codes clear of the including program's `ErrorCode`
+/// (6000), `ReentrancyError` (7000), `CastError` (7100) and `VersionError` (7200)
+#[error_code(offset = 7300)]
+pub enum ExportError {
+    #[msg("Pass the accounts to export as remaining accounts")]
+    NoAccounts,
+    #[msg("Only this program's accounts can be exported")]
+    WrongOwner,
+    #[msg("An account was passed more than once")]
+    DuplicateAccount,
+    #[msg("Snapshot is larger than return data allows; export fewer accounts")]
+    TooLarge,
+}
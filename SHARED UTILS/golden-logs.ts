@@ -0,0 +1,109 @@
+import * as fs from "fs";
+import * as path from "path";
+import { PublicKey } from "@solana/web3.js";
+
+/**
+ * The `msg!` lines `programId` itself printed, in order, without the
+ * `Program log: ` prefix. Lines from programs it invoked (the System
+ * Program, a CPI target) and runtime lines (`invoke`, `consumed`,
+ * `success`) are dropped: they change with the runtime, not the program.
+ */
+export const programMessages = (logs: string[], programId: PublicKey): string[] => {
+  const id = programId.toBase58();
+  const stack: string[] = [];
+  const messages: string[] = [];
+
+  for (const line of logs) {
+    const invoke = line.match(/^Program (\w+) invoke \[\d+\]$/);
+    if (invoke) {
+      stack.push(invoke[1]);
+      continue;
+    }
+    if (/^Program \w+ (success|failed)/.test(line)) {
+      stack.pop();
+      continue;
+    }
+    if (line.startsWith("Program log: ") && stack[stack.length - 1] === id) {
+      messages.push(line.slice("Program log: ".length));
+    }
+  }
+  return messages;
+};
+
+// Any base58 run the length of a public key
+const BASE58_KEY = /\b[1-9A-HJ-NP-Za-km-z]{32,44}\b/g;
+
+/**
+ * A program's happy-path log, checked against a committed golden file:
+ *
+ *   const golden = new GoldenLog("golden/missing_signer_secure.log", { AUTHORITY: owner.publicKey });
+ *   golden.record("initialize", logs, program.programId);
+ *   ...
+ *   golden.assertMatches();
+ *
+ * Keys passed to the constructor print as `<NAME>`; any other key prints
+ * as `<KEY1>`, `<KEY2>`, ... in order of first appearance, so fresh
+ * keypairs on every run produce the same file. With `UPDATE_GOLDEN=1` in
+ * the environment, `assertMatches` rewrites the file instead of
+ * comparing - review the diff before committing it.
+ */
+export class GoldenLog {
+  private readonly lines: string[] = [];
+  private readonly placeholders = new Map<string, string>();
+
+  constructor(
+    private readonly file: string,
+    names: Record<string, PublicKey> = {},
+  ) {
+    for (const [name, key] of Object.entries(names)) {
+      this.placeholders.set(key.toBase58(), `<${name}>`);
+    }
+  }
+
+  /** Appends `instruction`'s messages under a `== instruction ==` header */
+  record(instruction: string, logs: string[], programId: PublicKey): string[] {
+    const messages = programMessages(logs, programId).map((line) => this.normalize(line));
+    this.lines.push(`== ${instruction} ==`, ...messages);
+    return messages;
+  }
+
+  normalize(line: string): string {
+    return line.replace(BASE58_KEY, (key) => {
+      if (!this.placeholders.has(key)) {
+        const unnamed = [...this.placeholders.values()].filter((p) => p.startsWith("<KEY")).length;
+        this.placeholders.set(key, `<KEY${unnamed + 1}>`);
+      }
+      return this.placeholders.get(key);
+    });
+  }
+
+  text(): string {
+    return this.lines.join("\n") + "\n";
+  }
+
+  assertMatches(): void {
+    const actual = this.text();
+    if (process.env.UPDATE_GOLDEN) {
+      fs.mkdirSync(path.dirname(this.file), { recursive: true });
+      fs.writeFileSync(this.file, actual);
+      return;
+    }
+    if (!fs.existsSync(this.file)) {
+      throw new Error(`No golden file at ${this.file}; run with UPDATE_GOLDEN=1 to create it`);
+    }
+
+    const expected = fs.readFileSync(this.file, "utf8");
+    if (actual === expected) return;
+
+    const want = expected.split("\n");
+    const got = actual.split("\n");
+    const at = want.findIndex((line, i) => line !== got[i]);
+    const line = at === -1 ? want.length : at;
+    throw new Error(
+      `Log output drifted from ${this.file} at line ${line + 1}:\n` +
+      `  expected: ${JSON.stringify(want[line] ?? "<end of file>")}\n` +
+      `  actual:   ${JSON.stringify(got[line] ?? "<end of output>")}\n\n` +
+      `If the change is intended, rerun with UPDATE_GOLDEN=1 and commit the new file.\n\n${actual}`,
+    );
+  }
+}
@@ -0,0 +1,550 @@
+//! Decodes a program's accounts into stable JSON, driven by its IDL
+//!
+//! Off-chain: this runs in an auditor's tool, not in a program. It needs
+//! `anchor-lang-idl-spec`, `serde_json` (without `preserve_order`, so
+//! object keys come out sorted), `solana-client`, `solana-sdk` and `base64`.
+//!
+//! Nothing here knows any example's structs. An account's discriminator
+//! picks its type from `idl.accounts`, and the type's fields from
+//! `idl.types` say how to read the Borsh bytes that follow. A program that
+//! adds an account type needs a new IDL, not a new decoder.
+//!
+//! The JSON is the same for the same bytes, whoever produces it:
+//!
+//! | Borsh | JSON |
+//! |-------|------|
+//! | `u8`..`u32`, `i8`..`i32`, floats, `bool` | number, boolean |
+//! | `u64`, `i64`, `u128`, `i128` | decimal string, so JavaScript can't round it |
+//! | `pubkey` | base58 string |
+//! | `string` | string |
+//! | `bytes`, `Vec<u8>`, `[u8; N]` | lowercase hex string |
+//! | `Option<T>` | `null` or `T` |
+//! | struct | object, keys sorted |
+//! | enum | `"Variant"`, or `{ "Variant": fields }` |
+//!
+//! Snapshots are ordered by address. `fetch` and `decode_snapshot` give
+//! the same document for the same accounts in the same slot.
+//!
+//! `account_layout` walks the same types to build the **Account Layout**
+//! tables in the example readmes: each field's offset, size and type.
+
+use std::fmt;
+use std::fmt::Write as _;
+
+use anchor_lang_idl_spec::{
+    Idl, IdlArrayLen, IdlDefinedFields, IdlSerialization, IdlType, IdlTypeDef, IdlTypeDefTy,
+};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+
+/// The `Snapshot` layout `export.rs` writes
+pub const SNAPSHOT_FORMAT: u8 = 1;
+
+/// The instruction `fetch_export` simulates
+pub const EXPORT_INSTRUCTION: &str = "export_state";
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The data ended before the type did
+    UnexpectedEnd,
+    /// Bytes were left over after a snapshot
+    TrailingBytes(usize),
+    /// No account in the IDL has this data's discriminator
+    UnknownDiscriminator,
+    /// A `defined` type the IDL doesn't declare
+    UnknownType(String),
+    /// Valid IDL this decoder doesn't read: generics, zero-copy, 256-bit
+    Unsupported(String),
+    InvalidBool(u8),
+    /// An `Option` or enum tag out of range
+    InvalidTag(u8),
+    InvalidUtf8,
+    /// A snapshot written in another layout
+    UnknownFormat(u8),
+    MissingAccount(Pubkey),
+    WrongOwner(Pubkey),
+    /// The IDL has no `export_state` instruction
+    NoExportInstruction,
+    /// `export_state` failed; the error and its logs
+    Simulation(String),
+    Rpc(Box<ClientError>),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "data ended before the type did"),
+            Self::TrailingBytes(n) => write!(f, "{n} bytes left over after the snapshot"),
+            Self::UnknownDiscriminator => write!(f, "no IDL account has this discriminator"),
+            Self::UnknownType(name) => write!(f, "IDL does not declare type `{name}`"),
+            Self::Unsupported(what) => write!(f, "unsupported IDL type: {what}"),
+            Self::InvalidBool(b) => write!(f, "invalid bool byte {b}"),
+            Self::InvalidTag(t) => write!(f, "invalid option or enum tag {t}"),
+            Self::InvalidUtf8 => write!(f, "string is not UTF-8"),
+            Self::UnknownFormat(v) => write!(f, "unknown snapshot format {v}"),
+            Self::MissingAccount(key) => write!(f, "account {key} does not exist"),
+            Self::WrongOwner(key) => write!(f, "account {key} is not owned by the IDL's program"),
+            Self::NoExportInstruction => write!(f, "IDL has no `{EXPORT_INSTRUCTION}` instruction"),
+            Self::Simulation(err) => write!(f, "{EXPORT_INSTRUCTION} failed: {err}"),
+            Self::Rpc(err) => write!(f, "RPC error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<ClientError> for DecodeError {
+    fn from(err: ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
+
+type Result<T> = std::result::Result<T, DecodeError>;
+
+/// Borsh input, consumed from the front
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.data.len() < n {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (head, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("took N bytes"))
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    /// A Borsh length prefix. No element is smaller than a byte, so a
+    /// length past the end is refused before anything is read
+    fn length_prefix(&mut self) -> Result<usize> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        if len > self.data.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey> {
+        Ok(Pubkey::new_from_array(self.array()?))
+    }
+}
+
+fn hex(bytes: &[u8]) -> Value {
+    Value::String(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// The program the IDL describes
+pub fn program_id(idl: &Idl) -> Result<Pubkey> {
+    idl.address
+        .parse()
+        .map_err(|_| DecodeError::Unsupported(format!("program address `{}`", idl.address)))
+}
+
+/// Decodes one account's data, discriminator included. Returns the type's
+/// name and its fields. Bytes past the end of the type are ignored: an
+/// account is often allocated larger than its current value
+pub fn decode_account(idl: &Idl, data: &[u8]) -> Result<(String, Value)> {
+    let account = idl
+        .accounts
+        .iter()
+        .find(|account| data.starts_with(&account.discriminator))
+        .ok_or(DecodeError::UnknownDiscriminator)?;
+
+    let mut reader = Reader { data: &data[account.discriminator.len()..] };
+    let fields = decode_defined(idl, &account.name, &mut reader)?;
+    Ok((account.name.clone(), fields))
+}
+
+/// Decodes the return data of `export_state`
+pub fn decode_snapshot(idl: &Idl, return_data: &[u8]) -> Result<Value> {
+    let mut reader = Reader { data: return_data };
+
+    let format = reader.byte()?;
+    if format != SNAPSHOT_FORMAT {
+        return Err(DecodeError::UnknownFormat(format));
+    }
+    let slot = u64::from_le_bytes(reader.array()?);
+
+    let count = reader.length_prefix()?;
+    let mut accounts = Vec::with_capacity(count);
+    for _ in 0..count {
+        let address = reader.pubkey()?;
+        let len = reader.length_prefix()?;
+        accounts.push(entry(idl, &address, reader.take(len)?)?);
+    }
+    if !reader.data.is_empty() {
+        return Err(DecodeError::TrailingBytes(reader.data.len()));
+    }
+
+    Ok(snapshot(slot, accounts))
+}
+
+/// Reads `addresses` in one RPC call, so every account comes from the same
+/// slot, and decodes them. Each must be owned by the IDL's program
+pub fn fetch(client: &RpcClient, idl: &Idl, addresses: &[Pubkey]) -> Result<Value> {
+    let program_id = program_id(idl)?;
+    let mut addresses = addresses.to_vec();
+    addresses.sort();
+    addresses.dedup();
+
+    let response =
+        client.get_multiple_accounts_with_commitment(&addresses, CommitmentConfig::finalized())?;
+
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for (address, account) in addresses.iter().zip(response.value) {
+        let account = account.ok_or(DecodeError::MissingAccount(*address))?;
+        if account.owner != program_id {
+            return Err(DecodeError::WrongOwner(*address));
+        }
+        accounts.push(entry(idl, address, &account.data)?);
+    }
+
+    Ok(snapshot(response.context.slot, accounts))
+}
+
+/// Simulates the program's `export_state` over `addresses` and decodes the
+/// snapshot it returns. Nothing is signed or sent; `payer` only needs to
+/// exist
+pub fn fetch_export(
+    client: &RpcClient,
+    idl: &Idl,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Result<Value> {
+    let program_id = program_id(idl)?;
+    let export = idl
+        .instructions
+        .iter()
+        .find(|ix| ix.name == EXPORT_INSTRUCTION)
+        .ok_or(DecodeError::NoExportInstruction)?;
+
+    let metas = addresses.iter().map(|address| AccountMeta::new_readonly(*address, false));
+    let instruction = Instruction::new_with_bytes(program_id, &export.discriminator, metas.collect());
+    let transaction = Transaction::new_with_payer(&[instruction], Some(payer));
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(CommitmentConfig::finalized()),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = client.simulate_transaction_with_config(&transaction, config)?.value;
+
+    if let Some(err) = result.err {
+        let logs = result.logs.unwrap_or_default().join("\n");
+        return Err(DecodeError::Simulation(format!("{err}\n{logs}")));
+    }
+    let return_data = result
+        .return_data
+        .filter(|data| data.program_id == program_id.to_string())
+        .ok_or_else(|| DecodeError::Simulation("no return data".into()))?;
+    let bytes = BASE64
+        .decode(&return_data.data.0)
+        .map_err(|_| DecodeError::Simulation("return data is not base64".into()))?;
+
+    decode_snapshot(idl, &bytes)
+}
+
+/// One row of an account's layout table
+#[derive(Debug, PartialEq)]
+pub struct LayoutField {
+    pub name: String,
+    /// The Type cell as the readme tables write it, e.g. `` `Lamports` (`u64`) ``
+    pub ty: String,
+    /// `None` once an earlier field's size depends on its value
+    pub offset: Option<usize>,
+    /// `None` for `Option`, `Vec`, `String` and anything holding one
+    pub size: Option<usize>,
+}
+
+/// Where each field of an account type sits in its data
+#[derive(Debug, PartialEq)]
+pub struct AccountLayout {
+    pub name: String,
+    /// The discriminator first, then the fields in Borsh order
+    pub fields: Vec<LayoutField>,
+}
+
+impl AccountLayout {
+    /// Bytes every value takes, discriminator included, or `None` if a
+    /// field's size depends on its value
+    pub fn total(&self) -> Option<usize> {
+        self.fields.iter().map(|field| field.size).sum()
+    }
+
+    /// The table and total line, ready to paste under `### Account Layout`
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("| Offset | Size | Field | Type |\n|--------|------|-------|------|\n");
+        for (i, field) in self.fields.iter().enumerate() {
+            let offset = field.offset.map_or("—".into(), |offset| offset.to_string());
+            let size = field.size.map_or("variable".into(), |size| size.to_string());
+            let name = if i == 0 { field.name.clone() } else { format!("`{}`", field.name) };
+            let _ = writeln!(out, "| {offset} | {size} | {name} | {} |", field.ty);
+        }
+
+        let discriminator = self.fields[0].size.unwrap_or_default();
+        match self.total() {
+            Some(total) => {
+                let space = total - discriminator;
+                let _ = write!(out, "\n**Total:** {total} bytes = `{discriminator} + {}::INIT_SPACE` ({space})", self.name);
+            }
+            None => out.push_str("\n**Total:** not fixed; offsets after a variable-size field depend on its value"),
+        }
+        out
+    }
+}
+
+/// Lays out the account type `name` from the IDL. A field after an
+/// `Option`, `Vec` or `String` has no fixed offset: `None` is written as
+/// its tag alone, and `INIT_SPACE` only reserves the maximum
+pub fn account_layout(idl: &Idl, name: &str) -> Result<AccountLayout> {
+    let account = idl
+        .accounts
+        .iter()
+        .find(|account| account.name == name)
+        .ok_or_else(|| DecodeError::UnknownType(name.into()))?;
+    let IdlTypeDefTy::Struct { fields } = &type_def(idl, name)?.ty else {
+        return Err(DecodeError::Unsupported(format!("account `{name}` is not a struct")));
+    };
+
+    let discriminator = account.discriminator.len();
+    let mut rows = vec![LayoutField {
+        name: "discriminator".into(),
+        ty: format!("`sha256(\"account:{name}\")[..{discriminator}]`"),
+        offset: Some(0),
+        size: Some(discriminator),
+    }];
+
+    let fields: Vec<(String, &IdlType)> = match fields {
+        None => Vec::new(),
+        Some(IdlDefinedFields::Named(fields)) => fields.iter().map(|f| (f.name.clone(), &f.ty)).collect(),
+        Some(IdlDefinedFields::Tuple(types)) => types.iter().enumerate().map(|(i, ty)| (i.to_string(), ty)).collect(),
+    };
+    let mut offset = Some(discriminator);
+    for (field, ty) in fields {
+        let size = fixed_size(idl, ty)?;
+        rows.push(LayoutField { name: field, ty: type_cell(idl, ty)?, offset, size });
+        offset = offset.zip(size).map(|(offset, size)| offset + size);
+    }
+
+    Ok(AccountLayout { name: name.into(), fields: rows })
+}
+
+fn snapshot(slot: u64, accounts: Vec<Value>) -> Value {
+    json!({ "slot": slot.to_string(), "accounts": accounts })
+}
+
+fn entry(idl: &Idl, address: &Pubkey, data: &[u8]) -> Result<Value> {
+    let (name, fields) = decode_account(idl, data)?;
+    Ok(json!({ "address": address.to_string(), "type": name, "fields": fields }))
+}
+
+/// The IDL's definition of `name`, if this module can read it
+fn type_def<'a>(idl: &'a Idl, name: &str) -> Result<&'a IdlTypeDef> {
+    let def = idl
+        .types
+        .iter()
+        .find(|def| def.name == name)
+        .ok_or_else(|| DecodeError::UnknownType(name.into()))?;
+
+    if !def.generics.is_empty() {
+        return Err(DecodeError::Unsupported(format!("generic type `{name}`")));
+    }
+    // Zero-copy types are laid out with C padding, not Borsh
+    if !matches!(def.serialization, IdlSerialization::Borsh) {
+        return Err(DecodeError::Unsupported(format!("non-Borsh type `{name}`")));
+    }
+    Ok(def)
+}
+
+fn decode_defined(idl: &Idl, name: &str, reader: &mut Reader) -> Result<Value> {
+    match &type_def(idl, name)?.ty {
+        IdlTypeDefTy::Struct { fields } => decode_fields(idl, fields.as_ref(), reader),
+        IdlTypeDefTy::Enum { variants } => {
+            let tag = reader.byte()?;
+            let variant = variants.get(tag as usize).ok_or(DecodeError::InvalidTag(tag))?;
+            match &variant.fields {
+                None => Ok(Value::String(variant.name.clone())),
+                Some(fields) => {
+                    let fields = decode_fields(idl, Some(fields), reader)?;
+                    Ok(json!({ variant.name.clone(): fields }))
+                }
+            }
+        }
+        IdlTypeDefTy::Type { alias } => decode_type(idl, alias, reader),
+    }
+}
+
+fn decode_fields(idl: &Idl, fields: Option<&IdlDefinedFields>, reader: &mut Reader) -> Result<Value> {
+    match fields {
+        None => Ok(Value::Object(Map::new())),
+        Some(IdlDefinedFields::Named(fields)) => {
+            let mut object = Map::new();
+            for field in fields {
+                object.insert(field.name.clone(), decode_type(idl, &field.ty, reader)?);
+            }
+            Ok(Value::Object(object))
+        }
+        Some(IdlDefinedFields::Tuple(types)) => types
+            .iter()
+            .map(|ty| decode_type(idl, ty, reader))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+    }
+}
+
+fn decode_type(idl: &Idl, ty: &IdlType, reader: &mut Reader) -> Result<Value> {
+    Ok(match ty {
+        IdlType::Bool => match reader.byte()? {
+            0 => Value::Bool(false),
+            1 => Value::Bool(true),
+            b => return Err(DecodeError::InvalidBool(b)),
+        },
+        IdlType::U8 => json!(reader.byte()?),
+        IdlType::I8 => json!(i8::from_le_bytes(reader.array()?)),
+        IdlType::U16 => json!(u16::from_le_bytes(reader.array()?)),
+        IdlType::I16 => json!(i16::from_le_bytes(reader.array()?)),
+        IdlType::U32 => json!(u32::from_le_bytes(reader.array()?)),
+        IdlType::I32 => json!(i32::from_le_bytes(reader.array()?)),
+        IdlType::F32 => json!(f32::from_le_bytes(reader.array()?)),
+        IdlType::F64 => json!(f64::from_le_bytes(reader.array()?)),
+        IdlType::U64 => Value::String(u64::from_le_bytes(reader.array()?).to_string()),
+        IdlType::I64 => Value::String(i64::from_le_bytes(reader.array()?).to_string()),
+        IdlType::U128 => Value::String(u128::from_le_bytes(reader.array()?).to_string()),
+        IdlType::I128 => Value::String(i128::from_le_bytes(reader.array()?).to_string()),
+        IdlType::Pubkey => Value::String(reader.pubkey()?.to_string()),
+        IdlType::String => {
+            let len = reader.length_prefix()?;
+            let text = std::str::from_utf8(reader.take(len)?).map_err(|_| DecodeError::InvalidUtf8)?;
+            Value::String(text.into())
+        }
+        IdlType::Bytes => {
+            let len = reader.length_prefix()?;
+            hex(reader.take(len)?)
+        }
+        IdlType::Option(inner) => match reader.byte()? {
+            0 => Value::Null,
+            1 => decode_type(idl, inner, reader)?,
+            tag => return Err(DecodeError::InvalidTag(tag)),
+        },
+        IdlType::Vec(inner) => {
+            let len = reader.length_prefix()?;
+            decode_items(idl, inner, len, reader)?
+        }
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => decode_items(idl, inner, *len, reader)?,
+        IdlType::Defined { name, generics } if generics.is_empty() => decode_defined(idl, name, reader)?,
+        other => return Err(DecodeError::Unsupported(format!("{other:?}"))),
+    })
+}
+
+/// `len` items of `ty`; bytes come out as one hex string, not a list of numbers
+fn decode_items(idl: &Idl, ty: &IdlType, len: usize, reader: &mut Reader) -> Result<Value> {
+    if matches!(ty, IdlType::U8) {
+        return Ok(hex(reader.take(len)?));
+    }
+    (0..len)
+        .map(|_| decode_type(idl, ty, reader))
+        .collect::<Result<Vec<_>>>()
+        .map(Value::Array)
+}
+
+/// Borsh size of `ty` if every value of it has the same size
+fn fixed_size(idl: &Idl, ty: &IdlType) -> Result<Option<usize>> {
+    Ok(match ty {
+        IdlType::Bool | IdlType::U8 | IdlType::I8 => Some(1),
+        IdlType::U16 | IdlType::I16 => Some(2),
+        IdlType::U32 | IdlType::I32 | IdlType::F32 => Some(4),
+        IdlType::U64 | IdlType::I64 | IdlType::F64 => Some(8),
+        IdlType::U128 | IdlType::I128 => Some(16),
+        IdlType::Pubkey => Some(32),
+        IdlType::Option(_) | IdlType::Vec(_) | IdlType::String | IdlType::Bytes => None,
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => fixed_size(idl, inner)?.map(|size| size * len),
+        IdlType::Defined { name, generics } if generics.is_empty() => match &type_def(idl, name)?.ty {
+            IdlTypeDefTy::Struct { fields } => fields_size(idl, fields.as_ref())?,
+            // The tag, then the variant's fields: fixed only if every variant is the same size
+            IdlTypeDefTy::Enum { variants } => {
+                let sizes = variants
+                    .iter()
+                    .map(|variant| fields_size(idl, variant.fields.as_ref()))
+                    .collect::<Result<Vec<_>>>()?;
+                match sizes.first() {
+                    Some(first) if sizes.iter().all(|size| size == first) => first.map(|size| 1 + size),
+                    _ => None,
+                }
+            }
+            IdlTypeDefTy::Type { alias } => fixed_size(idl, alias)?,
+        },
+        other => return Err(DecodeError::Unsupported(format!("{other:?}"))),
+    })
+}
+
+fn fields_size(idl: &Idl, fields: Option<&IdlDefinedFields>) -> Result<Option<usize>> {
+    let types: Vec<&IdlType> = match fields {
+        None => Vec::new(),
+        Some(IdlDefinedFields::Named(fields)) => fields.iter().map(|field| &field.ty).collect(),
+        Some(IdlDefinedFields::Tuple(types)) => types.iter().collect(),
+    };
+    let sizes = types.into_iter().map(|ty| fixed_size(idl, ty)).collect::<Result<Vec<_>>>()?;
+    Ok(sizes.into_iter().sum())
+}
+
+/// `ty` in Rust syntax; a one-field wrapper also shows what it wraps, as
+/// `` `Lamports` (`u64`) ``
+fn type_cell(idl: &Idl, ty: &IdlType) -> Result<String> {
+    let name = type_name(ty)?;
+    if let IdlType::Defined { name: defined, .. } = ty {
+        let inner = match &type_def(idl, defined)?.ty {
+            IdlTypeDefTy::Struct { fields: Some(IdlDefinedFields::Tuple(types)) } if types.len() == 1 => Some(&types[0]),
+            IdlTypeDefTy::Type { alias } => Some(alias),
+            _ => None,
+        };
+        if let Some(inner) = inner {
+            return Ok(format!("`{name}` (`{}`)", type_name(inner)?));
+        }
+    }
+    Ok(format!("`{name}`"))
+}
+
+fn type_name(ty: &IdlType) -> Result<String> {
+    Ok(match ty {
+        IdlType::Bool => "bool".into(),
+        IdlType::U8 => "u8".into(),
+        IdlType::I8 => "i8".into(),
+        IdlType::U16 => "u16".into(),
+        IdlType::I16 => "i16".into(),
+        IdlType::U32 => "u32".into(),
+        IdlType::I32 => "i32".into(),
+        IdlType::F32 => "f32".into(),
+        IdlType::U64 => "u64".into(),
+        IdlType::I64 => "i64".into(),
+        IdlType::F64 => "f64".into(),
+        IdlType::U128 => "u128".into(),
+        IdlType::I128 => "i128".into(),
+        IdlType::Pubkey => "Pubkey".into(),
+        IdlType::String => "String".into(),
+        IdlType::Bytes => "Vec<u8>".into(),
+        IdlType::Option(inner) => format!("Option<{}>", type_name(inner)?),
+        IdlType::Vec(inner) => format!("Vec<{}>", type_name(inner)?),
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => format!("[{}; {len}]", type_name(inner)?),
+        IdlType::Defined { name, generics } if generics.is_empty() => name.clone(),
+        other => return Err(DecodeError::Unsupported(format!("{other:?}"))),
+    })
+}
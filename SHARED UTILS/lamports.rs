@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+/// An amount of SOL in lamports
+///
+/// Wrapping the `u64` keeps lamport balances from being added to token
+/// amounts, points or timestamps by accident: `Lamports + u64` does not
+/// compile. Serialized exactly like a `u64` (8 bytes, little-endian), so
+/// swapping a `balance: u64` field for `balance: Lamports` keeps the
+/// on-chain layout unchanged.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct Lamports(pub u64);
+
+impl Lamports {
+    pub const ZERO: Lamports = Lamports(0);
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    ///  Returns None on overflow instead of wrapping
+    pub fn checked_add(self, other: Lamports) -> Option<Lamports> {
+        self.0.checked_add(other.0).map(Lamports)
+    }
+
+    ///  Returns None on underflow instead of wrapping
+    pub fn checked_sub(self, other: Lamports) -> Option<Lamports> {
+        self.0.checked_sub(other.0).map(Lamports)
+    }
+
+    /// Scales by a plain count (e.g. fee per item), never by another amount
+    pub fn checked_mul(self, factor: u64) -> Option<Lamports> {
+        self.0.checked_mul(factor).map(Lamports)
+    }
+
+    pub fn checked_div(self, divisor: u64) -> Option<Lamports> {
+        self.0.checked_div(divisor).map(Lamports)
+    }
+}
+
+/// Same size as the wrapped `u64`, so `#[derive(InitSpace)]` works on
+/// structs with `Lamports` fields
+impl Space for Lamports {
+    const INIT_SPACE: usize = 8;
+}
+
+impl From<u64> for Lamports {
+    fn from(value: u64) -> Self {
+        Lamports(value)
+    }
+}
+
+impl From<Lamports> for u64 {
+    fn from(value: Lamports) -> Self {
+        value.0
+    }
+}
+
+/// For results of wide intermediate math (`u128`) that must fit back in a u64
+impl TryFrom<u128> for Lamports {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: u128) -> std::result::Result<Self, Self::Error> {
+        u64::try_from(value).map(Lamports)
+    }
+}
+
+impl std::fmt::Display for Lamports {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,44 @@
+//! Feature-gated wrappers around `msg!`
+//!
+//! Every `msg!` costs compute: roughly 100 CU for a plain string and several
+//! hundred more once it formats arguments. The examples log generously for
+//! teaching; these macros let the same source build quiet for benchmarks.
+//!
+//! | Cargo feature   | `log_msg!` | `audit_msg!` |
+//! |-----------------|------------|--------------|
+//! | *(none)*        | off        | off          |
+//! | `audit-logs`    | off        | on           |
+//! | `verbose-logs`  | on         | on           |
+//!
+//! The features belong to the program crate that includes this file, so
+//! its `Cargo.toml` declares them (see SHARED UTILS/readme.md).
+
+/// Narration for readers following along: "Vault initialized", "Deposited 5".
+/// Emitted only with `verbose-logs`.
+///
+/// `cfg!` rather than `#[cfg]` keeps the arguments type-checked (and used)
+/// in quiet builds; the branch is a constant `false` and the optimizer
+/// removes the `msg!` call and its formatting entirely.
+macro_rules! log_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
+/// Security-relevant events an indexer or auditor should see: funds moved,
+/// authority changed. Emitted with `audit-logs` or `verbose-logs`.
+///
+/// Never put seeds, bumps or other secrets here (see MISSING SIGNER CHECK,
+/// "Log Hygiene Test").
+macro_rules! audit_msg {
+    ($($arg:tt)*) => {
+        if cfg!(any(feature = "audit-logs", feature = "verbose-logs")) {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use audit_msg;
+pub(crate) use log_msg;
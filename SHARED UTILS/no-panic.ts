@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+
+const U64_MAX = new anchor.BN("18446744073709551615");
+
+/** Boundary values every numeric argument should be tried with */
+export const U64_EDGES: anchor.BN[] = [
+  new anchor.BN(0),
+  new anchor.BN(1),
+  new anchor.BN(2),
+  new anchor.BN(100),
+  new anchor.BN(0xffff_ffff),                    // u32::MAX
+  new anchor.BN(Number.MAX_SAFE_INTEGER),        // 2^53 - 1, where JS numbers stop being exact
+  new anchor.BN(1).shln(63).subn(1),             // i64::MAX
+  new anchor.BN(1).shln(63),
+  U64_MAX.subn(1),
+  U64_MAX,
+];
+
+// Same generator as the AMM INVARIANT simulation: seeded, so a failure replays
+const mulberry32 = (seed: number) => () => {
+  seed = (seed + 0x6d2b79f5) | 0;
+  let t = Math.imul(seed ^ (seed >>> 15), 1 | seed);
+  t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t;
+  return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+};
+
+/**
+ * `U64_EDGES` followed by `count` random u64s from `seed`. Random values
+ * are drawn at a random bit width so small numbers show up as often as
+ * huge ones. Duplicates are dropped: the same instruction with the same
+ * argument twice would be rejected as an already-processed transaction.
+ */
+export const fuzzU64 = (seed: number, count: number): anchor.BN[] => {
+  const next = mulberry32(seed);
+  const random = Array.from({ length: count }, () => {
+    const bits = 1 + Math.floor(next() * 64);
+    const hi = Math.floor(next() * 2 ** 32);
+    const lo = Math.floor(next() * 2 ** 32);
+    return new anchor.BN(hi).shln(32).or(new anchor.BN(lo)).maskn(bits);
+  });
+  const unique = new Map([...U64_EDGES, ...random].map((value) => [value.toString(), value]));
+  return [...unique.values()];
+};
+
+/**
+ * `count` random buffers of 0 to `maxLen` bytes from `seed`, for programs
+ * that parse `instruction_data` by hand. Lengths are drawn uniformly, so
+ * the empty and short inputs that break fixed-offset slicing come up as
+ * often as full-length ones. With `tags`, the first byte is picked from
+ * that list, so most buffers get past the tag dispatch and reach the
+ * argument parser. Duplicates are dropped, as in `fuzzU64`.
+ */
+export const fuzzBytes = (seed: number, count: number, maxLen: number, tags?: number[]): Buffer[] => {
+  const next = mulberry32(seed);
+  const random = Array.from({ length: count }, () => {
+    const data = Buffer.alloc(Math.floor(next() * (maxLen + 1)));
+    for (let i = 0; i < data.length; i++) {
+      data[i] = Math.floor(next() * 256);
+    }
+    if (tags && data.length > 0) {
+      data[0] = tags[Math.floor(next() * tags.length)];
+    }
+    return data;
+  });
+  const unique = new Map([Buffer.alloc(0), ...random].map((data) => [data.toString("hex"), data]));
+  return [...unique.values()];
+};
+
+// What the runtime logs when a program aborts instead of returning an error
+const PANIC = /panicked at|ProgramFailedToComplete|Program failed to complete/i;
+
+/** Whether an error string or log dump shows a panic rather than a returned error */
+export const isPanic = (text: string): boolean => PANIC.test(text);
+
+/**
+ * Sends `tx` and classifies the result. Succeeds with "ok" or the name of
+ * one of the `allowed` errors; anything else - a panic above all - fails
+ * the test with the logs attached.
+ */
+export const expectNoPanic = async (
+  tx: Promise<unknown>,
+  allowed: string[],
+): Promise<string> => {
+  try {
+    await tx;
+    return "ok";
+  } catch (err) {
+    const text = `${err}\n${(err.logs ?? []).join("\n")}`;
+    if (isPanic(text)) {
+      throw new Error(`Program panicked instead of returning an error:\n${text}`);
+    }
+    const name = allowed.find((code) => text.includes(code));
+    if (!name) {
+      throw new Error(`Unexpected error (allowed: ${allowed.join(", ")}):\n${text}`);
+    }
+    return name;
+  }
+};
@@ -0,0 +1,177 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { ProgramTestContext } from "solana-bankrun";
+
+type Seed = Buffer | Uint8Array | string | PublicKey;
+
+const toSeed = (seed: Seed): Buffer =>
+  seed instanceof PublicKey ? seed.toBuffer()
+    : typeof seed === "string" ? Buffer.from(seed)
+    : Buffer.from(seed);
+
+/**
+ * Writes a program-owned account straight into a bankrun bank.
+ *
+ * Derives the PDA and its canonical bump from `seeds`, then encodes the
+ * account with the program's own coder, so the discriminator and layout
+ * always match the IDL. The coder has no defaults and throws on a missing
+ * field, so every field starts at zero (see `zeroed`) and the `with...`
+ * calls overwrite it; a `bump` field, if the account has one, starts at
+ * the canonical bump.
+ *
+ *   const vault = await PdaBuilder.for(program, "Vault", ["vault", owner])
+ *     .withAuthority(owner)
+ *     .withBalance(5 * LAMPORTS_PER_SOL)
+ *     .build(context);
+ */
+export class PdaBuilder {
+  readonly address: PublicKey;
+  readonly bump: number;
+
+  private readonly seeds: Buffer[];
+  private target: PublicKey;
+  private fields: Record<string, unknown> = {};
+  private lamports = 0;
+  private space?: number;
+
+  private constructor(
+    private readonly program: anchor.Program,
+    private readonly accountName: string,
+    seeds: Seed[],
+  ) {
+    this.seeds = seeds.map(toSeed);
+    [this.address, this.bump] = PublicKey.findProgramAddressSync(this.seeds, program.programId);
+    this.target = this.address;
+    this.fields = this.zeroedDefined(accountName) as Record<string, unknown>;
+    if (this.fieldType("bump") !== undefined) this.fields.bump = this.bump;
+  }
+
+  static for(program: anchor.Program, accountName: string, seeds: Seed[]): PdaBuilder {
+    return new PdaBuilder(program, accountName, seeds);
+  }
+
+  /** Set any fields by their IDL (camelCase) names */
+  with(fields: Record<string, unknown>): this {
+    Object.assign(this.fields, fields);
+    return this;
+  }
+
+  /** Store `key` in the authority field (`authority` unless named otherwise) */
+  withAuthority(key: PublicKey, field = "authority"): this {
+    this.fields[field] = key;
+    return this;
+  }
+
+  /**
+   * Fund the account with `lamports` above rent exemption and, if it has a
+   * `balance` field, record the same amount there. Handles both a plain
+   * `u64` and a single-field newtype such as `Lamports(u64)`.
+   */
+  withBalance(lamports: number): this {
+    this.lamports = lamports;
+    const type = this.fieldType("balance");
+    if (type === "u64") this.fields.balance = new anchor.BN(lamports);
+    else if (type !== undefined) this.fields.balance = [new anchor.BN(lamports)];
+    return this;
+  }
+
+  /** Allocate `bytes` of data instead of the encoded length (e.g. for `#[max_len]` fields) */
+  withSpace(bytes: number): this {
+    this.space = bytes;
+    return this;
+  }
+
+  /**
+   * Store a bump that is valid for these seeds but not canonical, for
+   * negative tests. Falls back to an invalid bump when the seeds have no
+   * other valid one. The address stays the canonical PDA.
+   */
+  corruptBump(field = "bump"): this {
+    let corrupted = (this.bump + 255) % 256;
+    for (let bump = this.bump - 1; bump >= 0; bump--) {
+      try {
+        PublicKey.createProgramAddressSync([...this.seeds, Buffer.from([bump])], this.program.programId);
+        corrupted = bump;
+        break;
+      } catch {
+        // on the curve: not a valid bump, keep looking
+      }
+    }
+    this.fields[field] = corrupted;
+    return this;
+  }
+
+  /** Write the account at `address` instead of the PDA, for forged-account tests */
+  atAddress(address: PublicKey): this {
+    this.target = address;
+    return this;
+  }
+
+  /** Encode, fund and write the account; returns the address it was written to */
+  async build(context: ProgramTestContext): Promise<PublicKey> {
+    const encoded = await this.program.coder.accounts.encode(this.accountName, this.fields);
+    const data = Buffer.alloc(Math.max(this.space ?? 0, encoded.length));
+    encoded.copy(data);
+
+    const rent = await context.banksClient.getRent();
+    context.setAccount(this.target, {
+      lamports: Number(rent.minimumBalance(BigInt(data.length))) + this.lamports,
+      data,
+      owner: this.program.programId,
+      executable: false,
+    });
+    return this.target;
+  }
+
+  /**
+   * The zero value of an IDL type: `0`, `false`, the default `PublicKey`,
+   * an empty string or vector, `None`, and an enum's first variant
+   */
+  private zeroed(type: any): unknown {
+    if (typeof type === "string") {
+      switch (type) {
+        case "bool": return false;
+        case "u8": case "i8": case "u16": case "i16": case "u32": case "i32": case "f32": case "f64": return 0;
+        case "pubkey": return PublicKey.default;
+        case "string": return "";
+        case "bytes": return Buffer.alloc(0);
+        default: return new anchor.BN(0);  // u64 and wider
+      }
+    }
+    if ("option" in type || "coption" in type) return null;
+    if ("vec" in type) return [];
+    if ("array" in type) {
+      const [inner, len] = type.array;
+      return Array.from({ length: len }, () => this.zeroed(inner));
+    }
+    if ("defined" in type) return this.zeroedDefined(type.defined.name);
+    throw new Error(`PdaBuilder: no zero value for ${JSON.stringify(type)}`);
+  }
+
+  private zeroedDefined(name: string): unknown {
+    const def = this.program.idl.types?.find((t) => t.name === name);
+    if (def === undefined) throw new Error(`PdaBuilder: IDL has no type ${name}`);
+    const ty = def.type as any;
+    if (ty.kind === "type") return this.zeroed(ty.alias);
+    if (ty.kind === "enum") {
+      const [first] = ty.variants;
+      return { [first.name]: this.zeroedFields(first.fields) };
+    }
+    return this.zeroedFields(ty.fields);
+  }
+
+  /** Named fields as an object, tuple fields as an array (as `withBalance` writes `Lamports`) */
+  private zeroedFields(fields: any[] | undefined): unknown {
+    if (fields === undefined || fields.length === 0) return {};
+    if (typeof fields[0] === "object" && "name" in fields[0]) {
+      return Object.fromEntries(fields.map((f) => [f.name, this.zeroed(f.type)]));
+    }
+    return fields.map((f) => this.zeroed(f));
+  }
+
+  private fieldType(name: string): unknown {
+    const def = this.program.idl.types?.find((t) => t.name === this.accountName);
+    if (def?.type.kind !== "struct") return undefined;
+    return (def.type.fields as { name: string; type: unknown }[])?.find((f) => f.name === name)?.type;
+  }
+}
@@ -0,0 +1,32 @@
+//! Basis-point percentages of `u64` amounts
+//!
+//! `amount * bps / 10_000` in `u64` overflows once `amount` passes about
+//! 1.8e15, and `points / 100` hides a 1% rate inside a divisor nobody can
+//! review. Every percentage goes through here instead: the rate is an
+//! explicit `bps` (1 bps = 0.01%), the product is taken in `u128`, and the
+//! result comes back through `safe_cast`, so a rate above 100% that
+//! doesn't fit in a `u64` is an error rather than a wrapped number.
+//!
+//! Needs `safe_cast.rs` included next to it as `mod safe_cast;`.
+
+use anchor_lang::prelude::*;
+
+use crate::safe_cast;
+
+/// 100% in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// `amount * bps / 10_000`, rounded down. For what the program pays out:
+/// rewards, shares, interest
+pub fn percentage_of(amount: u64, bps: u16) -> Result<u64> {
+    // At most 2^64 * 2^16: the product can't overflow a u128
+    let wide = amount as u128 * bps as u128 / BPS_DENOMINATOR as u128;
+    safe_cast::u128_to_u64(wide)
+}
+
+/// `amount * bps / 10_000`, rounded up. For what the program charges: a
+/// fee is never rounded down to zero, however small the amount
+pub fn percentage_of_ceil(amount: u64, bps: u16) -> Result<u64> {
+    let wide = (amount as u128 * bps as u128).div_ceil(BPS_DENOMINATOR as u128);
+    safe_cast::u128_to_u64(wide)
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+/// A re-entry lock stored inside an `#[account]`
+///
+/// Embed it as a field of the account a CPI must not be able to reach
+/// again mid-instruction, call `enter` before the CPI and `exit` after it.
+/// One byte on chain; `#[derive(InitSpace)]` on the parent keeps working.
+///
+/// `enter` only changes the deserialized copy. Anchor writes accounts back
+/// when the handler returns, so a callee would still read `entered: false`.
+/// Persist the flag before invoking:
+///
+/// ```ignore
+/// ctx.accounts.pool.guard.enter()?;
+/// ctx.accounts.pool.exit(&crate::ID)?;   // flush `entered = true` to account data
+/// invoke(&ix, &accounts)?;
+/// ctx.accounts.pool.guard.exit();
+/// ```
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct ReentrancyGuard {
+    pub entered: bool,
+}
+
+impl ReentrancyGuard {
+    ///  Fails if an outer frame of this instruction already holds the lock
+    pub fn enter(&mut self) -> Result<()> {
+        require!(!self.entered, ReentrancyError::Reentered);
+        self.entered = true;
+        Ok(())
+    }
+
+    pub fn exit(&mut self) {
+        self.entered = false;
+    }
+}
+
+/// Offset keeps these codes clear of the including program's `ErrorCode`,
+/// which starts at 6000
+#[error_code(offset = 7000)]
+pub enum ReentrancyError {
+    #[msg("Re-entrant call rejected")]
+    Reentered,
+}
@@ -0,0 +1,49 @@
+//! Integer conversions that fail instead of truncating
+//!
+//! `value as u32` keeps the low 32 bits of a `u64` and says nothing, and
+//! `-1i64 as u64` is `u64::MAX`. Every narrowing or sign-changing
+//! conversion in a program goes through one of these instead, so an
+//! out-of-range value becomes `CastError::OutOfRange` rather than a
+//! different, valid-looking number. Widening casts (`u32 as u64`,
+//! `u64 as u128`) can't lose anything and stay as `as`.
+
+use anchor_lang::prelude::*;
+
+fn narrow<T, U: TryFrom<T>>(value: T) -> Result<U> {
+    U::try_from(value).map_err(|_| error!(CastError::OutOfRange))
+}
+
+pub fn u64_to_u32(value: u64) -> Result<u32> {
+    narrow(value)
+}
+
+pub fn u64_to_u16(value: u64) -> Result<u16> {
+    narrow(value)
+}
+
+pub fn u64_to_u8(value: u64) -> Result<u8> {
+    narrow(value)
+}
+
+/// For results of wide intermediate math that must fit back in a `u64`
+pub fn u128_to_u64(value: u128) -> Result<u64> {
+    narrow(value)
+}
+
+/// Rejects negative values, e.g. a timestamp difference that went backwards
+pub fn i64_to_u64(value: i64) -> Result<u64> {
+    narrow(value)
+}
+
+/// Rejects values above `i64::MAX`, e.g. a duration added to a timestamp
+pub fn u64_to_i64(value: u64) -> Result<i64> {
+    narrow(value)
+}
+
+/// Offset keeps these codes clear of the including program's `ErrorCode`
+/// (6000) and of `ReentrancyError` (7000)
+#[error_code(offset = 7100)]
+pub enum CastError {
+    #[msg("Value out of range for the target integer type")]
+    OutOfRange,
+}
@@ -0,0 +1,73 @@
+import { BanksClient } from "solana-bankrun";
+import { Keypair, Transaction, TransactionInstruction } from "@solana/web3.js";
+
+/** What a transaction would have done, had it been sent */
+export interface Simulation {
+  /** `null` if it would succeed, otherwise the runtime's error string */
+  error: string | null;
+  /** `false` if it was refused before any instruction ran (bad signature, unknown blockhash) */
+  executed: boolean;
+  logs: string[];
+  unitsConsumed: number;
+}
+
+/**
+ * Runs `instructions` against the current bank state without committing
+ * anything - what a client's pre-flight check does. Accounts, balances and
+ * the blockhash queue are exactly as before the call, so one bank can be
+ * reused for any number of rejection checks.
+ */
+export const simulate = async (
+  banksClient: BanksClient,
+  payer: Keypair,
+  instructions: TransactionInstruction[],
+  signers: Keypair[] = [],
+): Promise<Simulation> => {
+  const [blockhash] = await banksClient.getLatestBlockhash();
+  const tx = new Transaction();
+  tx.recentBlockhash = blockhash;
+  tx.feePayer = payer.publicKey;
+  tx.add(...instructions);
+  tx.sign(payer, ...signers);
+
+  const { result, meta } = await banksClient.simulateTransaction(tx);
+  // No meta: the bank refused the transaction before executing it
+  if (!meta) {
+    return { error: result ?? "Simulation did not run", executed: false, logs: [], unitsConsumed: 0 };
+  }
+  return {
+    error: result,
+    executed: true,
+    logs: meta.logMessages,
+    unitsConsumed: Number(meta.computeUnitsConsumed),
+  };
+};
+
+/** The Anchor error name (`ConstraintSeeds`, `InsufficientFunds`, ...) in the logs, if any */
+export const anchorErrorName = (logs: string[]): string | undefined => {
+  for (const line of logs) {
+    const match = line.match(/Error Code: (\w+)\./);
+    if (match) return match[1];
+  }
+  return undefined;
+};
+
+/**
+ * Asserts the simulation ran and failed with the Anchor error `code`.
+ * Throws with the logs attached otherwise: a success, a different error
+ * and a transaction that never executed are all wrong answers.
+ */
+export const expectRejected = (sim: Simulation, code: string): Simulation => {
+  const logs = sim.logs.join("\n");
+  if (!sim.executed) {
+    throw new Error(`Expected ${code}, but the transaction never executed: ${sim.error}`);
+  }
+  if (sim.error === null) {
+    throw new Error(`Expected ${code}, but the transaction would succeed:\n${logs}`);
+  }
+  const name = anchorErrorName(sim.logs);
+  if (name !== code) {
+    throw new Error(`Expected ${code}, got ${name ?? sim.error}:\n${logs}`);
+  }
+  return sim;
+};
@@ -0,0 +1,102 @@
+//! Accounts whose layout can change without old accounts being misread
+//!
+//! `#[account]` data is the discriminator followed by the fields, with
+//! nothing recording which layout wrote them. Change a field's type in a
+//! program upgrade and every account written before it still passes the
+//! owner and discriminator checks, so the new layout reads the old bytes as
+//! whatever they happen to line up with. A versioned account stores one
+//! more byte:
+//!
+//! ```text
+//! [discriminator: 8][version: u8][body, in that version's layout]
+//! ```
+//!
+//! `read_versioned` checks the owner, the discriminator and the version,
+//! then hands the body to the decoder for *that* version. Old layouts are
+//! converted to the current one in code, so a handler only ever sees the
+//! current struct. A version newer than the program knows is refused, not
+//! guessed at.
+//!
+//! The version byte has to be there from the first release. An account
+//! written without one can't be told apart from one written with it.
+
+use anchor_lang::prelude::*;
+
+/// Offset of the version byte, right after the discriminator
+pub const VERSION_OFFSET: usize = 8;
+
+/// Discriminator plus version byte; the body starts here
+pub const HEADER_LEN: usize = VERSION_OFFSET + 1;
+
+/// One account type, every layout it has ever been written in
+pub trait VersionedAccount: AnchorSerialize + Sized {
+    /// Shared by every version; the type doesn't change, only its layout.
+    /// Use `sha256("account:<Name>")[..8]` to stay readable by Anchor tooling
+    const DISCRIMINATOR: [u8; 8];
+
+    /// The version this program writes. Starts at 1 and only ever goes up
+    const VERSION: u8;
+
+    /// Decodes a body written as `version` and converts it to the current
+    /// layout. Only called with `1..=VERSION`
+    fn decode(version: u8, body: &mut &[u8]) -> Result<Self>;
+}
+
+/// Decodes one version's body struct, for use inside `VersionedAccount::decode`
+pub fn decode_body<B: AnchorDeserialize>(body: &mut &[u8]) -> Result<B> {
+    B::deserialize(body).map_err(|_| error!(VersionError::MalformedBody))
+}
+
+/// The stored version of `data`, once the discriminator has been checked
+pub fn version_of<T: VersionedAccount>(data: &[u8]) -> Result<u8> {
+    require!(data.len() >= HEADER_LEN, VersionError::AccountTooSmall);
+    require!(data[..VERSION_OFFSET] == T::DISCRIMINATOR, VersionError::WrongDiscriminator);
+
+    let version = data[VERSION_OFFSET];
+    // 0 is never written, and a newer version is a layout this build hasn't seen
+    require!((1..=T::VERSION).contains(&version), VersionError::UnknownVersion);
+    Ok(version)
+}
+
+/// Reads `info` as a `T`, whichever version wrote it
+pub fn read_versioned<T: VersionedAccount>(info: &AccountInfo, program_id: &Pubkey) -> Result<T> {
+    require_keys_eq!(*info.owner, *program_id, VersionError::WrongOwner);
+
+    let data = info.try_borrow_data()?;
+    let version = version_of::<T>(&data)?;
+    let mut body = &data[HEADER_LEN..];
+    T::decode(version, &mut body)
+}
+
+/// Writes `value` as the current version. The account must already be
+/// `HEADER_LEN` plus the body long; grow it first when upgrading
+pub fn write_versioned<T: VersionedAccount>(info: &AccountInfo, value: &T) -> Result<()> {
+    let body = value.try_to_vec().map_err(|_| error!(VersionError::MalformedBody))?;
+    let end = HEADER_LEN + body.len();
+
+    let mut data = info.try_borrow_mut_data()?;
+    require!(data.len() >= end, VersionError::AccountTooSmall);
+
+    data[..VERSION_OFFSET].copy_from_slice(&T::DISCRIMINATOR);
+    data[VERSION_OFFSET] = T::VERSION;
+    data[HEADER_LEN..end].copy_from_slice(&body);
+    // A shorter body must not leave an older layout's bytes behind it
+    data[end..].fill(0);
+    Ok(())
+}
+
+/// Offset keeps these codes clear of the including program's `ErrorCode`
+/// (6000), `ReentrancyError` (7000) and `CastError` (7100)
+#[error_code(offset = 7200)]
+pub enum VersionError {
+    #[msg("Versioned account is owned by another program")]
+    WrongOwner,
+    #[msg("Account is too small for a versioned header and body")]
+    AccountTooSmall,
+    #[msg("Account discriminator does not match the expected type")]
+    WrongDiscriminator,
+    #[msg("Account version is not one this program can read")]
+    UnknownVersion,
+    #[msg("Account body does not match its version's layout")]
+    MalformedBody,
+}
@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Recovery5ecur222222222222222222222222222222");
+
+/// Guardians per wallet; `threshold` of them must agree to start a recovery
+pub const GUARDIANS: usize = 3;
+
+/// How long the current authority has to cancel a recovery (3 days)
+pub const CHALLENGE_PERIOD_SECS: i64 = 3 * 24 * 60 * 60;
+
+#[program]
+pub mod social_recovery_secure {
+    use super::*;
+
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        guardians: [Pubkey; GUARDIANS],
+        threshold: u8,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+
+        //  FIX: 0 would let anyone recover; above GUARDIANS nobody could
+        require!(
+            threshold >= 1 && threshold as usize <= GUARDIANS,
+            ErrorCode::InvalidThreshold
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            //  FIX: A key listed twice would count twice
+            require!(!guardians[..i].contains(guardian), ErrorCode::DuplicateGuardian);
+            //  FIX: Whoever holds the authority key must not also hold a vote
+            require!(*guardian != authority, ErrorCode::AuthorityIsGuardian);
+        }
+
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.creator = authority;
+        wallet.authority = authority;
+        wallet.balance = 0;
+        wallet.bump = ctx.bumps.wallet;
+
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.wallet = wallet.key();
+        recovery.guardians = guardians;
+        recovery.threshold = threshold;
+        recovery.new_authority = None;
+        recovery.initiated_ts = 0;
+        recovery.bump = ctx.bumps.recovery;
+
+        msg!("Wallet created, {} of {} guardians can recover it", threshold, GUARDIANS);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.wallet.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.balance = wallet.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.balance = wallet.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        wallet.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: `threshold` distinct guardians propose a new authority
+    /// Guardian signatures are passed in remaining_accounts. Nothing changes
+    /// hands here; this only starts the challenge period
+    pub fn initiate_recovery<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitiateRecovery<'info>>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let recovery = &mut ctx.accounts.recovery;
+        //  FIX: One recovery at a time; a second can't restart the clock
+        require!(recovery.new_authority.is_none(), ErrorCode::RecoveryPending);
+
+        //  FIX: Count distinct guardians who actually signed
+        let mut approved: Vec<Pubkey> = Vec::with_capacity(GUARDIANS);
+        for account in ctx.remaining_accounts.iter() {
+            require!(account.is_signer, ErrorCode::MissingSignature);
+            require!(recovery.guardians.contains(account.key), ErrorCode::NotAGuardian);
+            require!(!approved.contains(account.key), ErrorCode::DuplicateGuardian);
+            approved.push(*account.key);
+        }
+        require!(
+            approved.len() >= recovery.threshold as usize,
+            ErrorCode::ThresholdNotMet
+        );
+
+        recovery.new_authority = Some(new_authority);
+        recovery.initiated_ts = Clock::get()?.unix_timestamp;
+        msg!(
+            " Recovery to {} started by {} of {} guardians",
+            new_authority,
+            approved.len(),
+            GUARDIANS
+        );
+        Ok(())
+    }
+
+    ///  SECURE: An authority that still holds its key vetoes the recovery
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        let recovery = &mut ctx.accounts.recovery;
+        require!(recovery.new_authority.is_some(), ErrorCode::NoRecoveryPending);
+
+        recovery.new_authority = None;
+        recovery.initiated_ts = 0;
+        msg!(" Recovery cancelled by the authority");
+        Ok(())
+    }
+
+    ///  SECURE: Once the challenge period has passed without a veto, the
+    /// proposed key takes over - by its own signature, so it's known to work
+    pub fn complete_recovery(ctx: Context<CompleteRecovery>) -> Result<()> {
+        let recovery = &mut ctx.accounts.recovery;
+        let new_authority = recovery.new_authority.ok_or(ErrorCode::NoRecoveryPending)?;
+        require_keys_eq!(
+            ctx.accounts.new_authority.key(),
+            new_authority,
+            ErrorCode::NotProposedAuthority
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let waited = now.checked_sub(recovery.initiated_ts).ok_or(ErrorCode::Overflow)?;
+        require!(waited >= CHALLENGE_PERIOD_SECS, ErrorCode::ChallengePeriodActive);
+
+        ctx.accounts.wallet.authority = new_authority;
+        recovery.new_authority = None;
+        recovery.initiated_ts = 0;
+        msg!(" Authority rotated to {}", new_authority);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Wallet::INIT_SPACE,
+        seeds = [b"wallet", authority.key().as_ref()],
+        bump
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Recovery::INIT_SPACE,
+        seeds = [b"recovery", wallet.key().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"wallet", wallet.creator.as_ref()],
+        bump = wallet.bump,
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        //  FIX: Seeds use the creator, so the address survives a rotation
+        seeds = [b"wallet", wallet.creator.as_ref()],
+        bump = wallet.bump,
+        has_one = authority,
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+///  SECURE: No single Signer field; guardian approvals come from remaining_accounts
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    #[account(seeds = [b"wallet", wallet.creator.as_ref()], bump = wallet.bump)]
+    pub wallet: Account<'info, Wallet>,
+    #[account(
+        mut,
+        seeds = [b"recovery", wallet.key().as_ref()],
+        bump = recovery.bump,
+        has_one = wallet,
+    )]
+    pub recovery: Account<'info, Recovery>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    #[account(
+        seeds = [b"wallet", wallet.creator.as_ref()],
+        bump = wallet.bump,
+        has_one = authority,
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(
+        mut,
+        seeds = [b"recovery", wallet.key().as_ref()],
+        bump = recovery.bump,
+        has_one = wallet,
+    )]
+    pub recovery: Account<'info, Recovery>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteRecovery<'info> {
+    #[account(mut, seeds = [b"wallet", wallet.creator.as_ref()], bump = wallet.bump)]
+    pub wallet: Account<'info, Wallet>,
+    #[account(
+        mut,
+        seeds = [b"recovery", wallet.key().as_ref()],
+        bump = recovery.bump,
+        has_one = wallet,
+    )]
+    pub recovery: Account<'info, Recovery>,
+    pub new_authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Wallet {
+    /// Fixed at creation; the wallet's address is derived from it
+    pub creator: Pubkey,
+    /// Current key; replaced by `complete_recovery`
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Recovery {
+    pub wallet: Pubkey,
+    pub guardians: [Pubkey; GUARDIANS],
+    pub threshold: u8,
+    /// Key the guardians proposed; `None` when no recovery is pending
+    pub new_authority: Option<Pubkey>,
+    /// When the pending recovery was started
+    pub initiated_ts: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in wallet")]
+    InsufficientFunds,
+    #[msg("Threshold must be between 1 and the number of guardians")]
+    InvalidThreshold,
+    #[msg("Guardian listed more than once")]
+    DuplicateGuardian,
+    #[msg("The authority can't be its own guardian")]
+    AuthorityIsGuardian,
+    #[msg("Guardian account did not sign")]
+    MissingSignature,
+    #[msg("Signer is not a guardian of this wallet")]
+    NotAGuardian,
+    #[msg("Not enough guardians approved the recovery")]
+    ThresholdNotMet,
+    #[msg("A recovery is already pending")]
+    RecoveryPending,
+    #[msg("No recovery is pending")]
+    NoRecoveryPending,
+    #[msg("Signer is not the proposed authority")]
+    NotProposedAuthority,
+    #[msg("The challenge period has not passed")]
+    ChallengePeriodActive,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Recoveryvu1n1111111111111111111111111111111");
+
+#[program]
+pub mod social_recovery_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.authority = ctx.accounts.authority.key();
+        wallet.balance = 0;
+        wallet.bump = ctx.bumps.wallet;
+        msg!("Wallet created for authority: {}", wallet.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.wallet.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.balance = wallet.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: One key, no way to replace it - lose the key, lose the wallet!
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.balance = wallet.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        wallet.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Wallet::INIT_SPACE,
+        seeds = [b"wallet", authority.key().as_ref()],
+        bump
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"wallet", wallet.authority.as_ref()],
+        bump = wallet.bump,
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+///  VULNERABLE: The address itself is derived from the authority, so even
+/// an upgrade that added rotation couldn't keep it
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"wallet", authority.key().as_ref()],
+        bump = wallet.bump,
+        has_one = authority,
+    )]
+    pub wallet: Account<'info, Wallet>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Wallet {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in wallet")]
+    InsufficientFunds,
+}
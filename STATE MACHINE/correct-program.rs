@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Stage5ecur222222222222222222222222222222222");
+
+#[program]
+pub mod state_machine_secure {
+    use super::*;
+
+    pub fn create_grant(ctx: Context<CreateGrant>, reviewer: Pubkey, amount: u64) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        grant.admin = ctx.accounts.admin.key();
+        grant.recipient = ctx.accounts.recipient.key();
+        grant.reviewer = reviewer;
+        grant.amount = amount;
+        grant.stage = Stage::Created;
+        grant.bump = ctx.bumps.grant;
+        msg!("Grant created for {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Created -> Funded only
+    pub fn fund(ctx: Context<Fund>) -> Result<()> {
+        require!(ctx.accounts.grant.stage == Stage::Created, ErrorCode::InvalidStage);
+
+        let amount = ctx.accounts.grant.amount;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.admin.to_account_info(),
+                to: ctx.accounts.grant.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        ctx.accounts.grant.stage = Stage::Funded;
+        msg!("Grant funded");
+        Ok(())
+    }
+
+    ///  SECURE: Funded -> Approved only
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        require!(grant.stage == Stage::Funded, ErrorCode::InvalidStage);
+
+        grant.stage = Stage::Approved;
+        msg!("Grant approved");
+        Ok(())
+    }
+
+    ///  SECURE: Approved -> Finalized only
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        //  FIX: One check covers "not funded", "not approved" and "already paid"
+        require!(grant.stage == Stage::Approved, ErrorCode::InvalidStage);
+
+        //  FIX: Move to the terminal stage before moving funds
+        grant.stage = Stage::Finalized;
+
+        let amount = grant.amount;
+        grant.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        msg!(" Grant paid out: {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Created | Funded -> Cancelled; refunds only what was funded
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+
+        match grant.stage {
+            Stage::Created => {}
+            Stage::Funded => {
+                let amount = grant.amount;
+                grant.sub_lamports(amount)?;
+                ctx.accounts.admin.add_lamports(amount)?;
+            }
+            //  Approved grants belong to the recipient; terminal stages are final
+            Stage::Approved | Stage::Finalized | Stage::Cancelled => {
+                return err!(ErrorCode::InvalidStage);
+            }
+        }
+
+        grant.stage = Stage::Cancelled;
+        msg!("Grant cancelled");
+        Ok(())
+    }
+}
+
+/// Grant lifecycle:
+///
+/// ```text
+/// Created ──fund──▶ Funded ──approve──▶ Approved ──finalize──▶ Finalized
+///    │                 │
+///    └────cancel───────┴──────────────▶ Cancelled
+/// ```
+///
+/// Borsh stores the variant *index* (one byte), not the `= N` value, so the
+/// explicit discriminants are kept equal to declaration order. Append new
+/// stages at the end; reordering would reinterpret existing accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+#[repr(u8)]
+pub enum Stage {
+    Created = 0,
+    Funded = 1,
+    Approved = 2,
+    Finalized = 3,
+    Cancelled = 4,
+}
+
+#[derive(Accounts)]
+pub struct CreateGrant<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Grant::INIT_SPACE,
+        seeds = [b"grant", admin.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, Grant>,
+    /// CHECK: Recipient of the grant
+    pub recipient: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Fund<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", admin.key().as_ref(), grant.recipient.as_ref()],
+        bump = grant.bump,
+        has_one = admin,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", grant.admin.as_ref(), grant.recipient.as_ref()],
+        bump = grant.bump,
+        has_one = reviewer,
+    )]
+    pub grant: Account<'info, Grant>,
+    pub reviewer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Finalize<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", grant.admin.as_ref(), recipient.key().as_ref()],
+        bump = grant.bump,
+        has_one = recipient,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", admin.key().as_ref(), grant.recipient.as_ref()],
+        bump = grant.bump,
+        has_one = admin,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Grant {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    ///  FIX: One field holds the whole lifecycle, replacing funded/approved flags
+    pub stage: Stage,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Instruction not allowed in the grant's current stage")]
+    InvalidStage,
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Stagevu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod state_machine_vulnerable {
+    use super::*;
+
+    pub fn create_grant(ctx: Context<CreateGrant>, reviewer: Pubkey, amount: u64) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        grant.admin = ctx.accounts.admin.key();
+        grant.recipient = ctx.accounts.recipient.key();
+        grant.reviewer = reviewer;
+        grant.amount = amount;
+        grant.funded = false;
+        grant.approved = false;
+        grant.bump = ctx.bumps.grant;
+        msg!("Grant created for {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn fund(ctx: Context<Fund>) -> Result<()> {
+        let amount = ctx.accounts.grant.amount;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.admin.to_account_info(),
+                to: ctx.accounts.grant.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        //  Boolean flags: nothing stops funding twice or after payout
+        ctx.accounts.grant.funded = true;
+        msg!("Grant funded");
+        Ok(())
+    }
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        //  Can approve a grant that was never funded
+        ctx.accounts.grant.approved = true;
+        msg!("Grant approved");
+        Ok(())
+    }
+
+    ///  VULNERABLE: Checks that the grant is funded but not that it was approved!
+    /// Recipient can skip the review step entirely
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+
+        require!(grant.funded, ErrorCode::NotFunded);
+        //  Missing: require!(grant.approved, ...)
+        //  Missing: anything marking the grant as paid
+
+        let amount = grant.amount;
+        grant.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        msg!(" Grant paid out: {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Refunds even after the grant was paid out
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+
+        if grant.funded {
+            let amount = grant.amount;
+            grant.sub_lamports(amount)?;
+            ctx.accounts.admin.add_lamports(amount)?;
+            grant.funded = false;
+        }
+        msg!("Grant cancelled");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateGrant<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Grant::INIT_SPACE,
+        seeds = [b"grant", admin.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, Grant>,
+    /// CHECK: Recipient of the grant
+    pub recipient: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Fund<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", admin.key().as_ref(), grant.recipient.as_ref()],
+        bump = grant.bump,
+        has_one = admin,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", grant.admin.as_ref(), grant.recipient.as_ref()],
+        bump = grant.bump,
+        has_one = reviewer,
+    )]
+    pub grant: Account<'info, Grant>,
+    pub reviewer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Finalize<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", grant.admin.as_ref(), recipient.key().as_ref()],
+        bump = grant.bump,
+        has_one = recipient,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    #[account(
+        mut,
+        seeds = [b"grant", admin.key().as_ref(), grant.recipient.as_ref()],
+        bump = grant.bump,
+        has_one = admin,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Grant {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    pub funded: bool,
+    pub approved: bool,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Grant is not funded")]
+    NotFunded,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as SplTransfer};
+
+declare_id!("SwapMintvu1n1111111111111111111111111111111");
+
+#[program]
+pub mod swap_mints_vulnerable {
+    use super::*;
+
+    /// A 1:1 pool between two pegged tokens with the same decimals
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        require!(
+            ctx.accounts.mint_a.decimals == ctx.accounts.mint_b.decimals,
+            ErrorCode::DecimalsMismatch
+        );
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.bump = ctx.bumps.pool;
+        msg!("Pool initialized: {} <-> {}", pool.mint_a, pool.mint_b);
+        Ok(())
+    }
+
+    ///  VULNERABLE: The accounts passed in decide which mints are swapped!
+    pub fn swap(ctx: Context<Swap>, amount: u64) -> Result<()> {
+        // Whatever mint user_in holds goes into whatever vault_in is...
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.user_in.to_account_info(),
+                to: ctx.accounts.vault_in.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        // ...and the pool pays out the same amount of whatever vault_out holds
+        let pool = &ctx.accounts.pool;
+        let seeds = &[
+            b"pool".as_ref(),
+            pool.mint_a.as_ref(),
+            pool.mint_b.as_ref(),
+            &[pool.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.vault_out.to_account_info(),
+                to: ctx.accounts.user_out.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!(" Swapped {} tokens", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault", pool.key().as_ref(), mint_a.key().as_ref()],
+        bump,
+        token::mint = mint_a,
+        token::authority = pool,
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault", pool.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = pool,
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+    pub mint_a: Account<'info, Mint>,
+    pub mint_b: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        seeds = [b"pool", pool.mint_a.as_ref(), pool.mint_b.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    ///  BUG: Any token account the pool owns - anyone can create one, of any mint
+    #[account(mut, token::authority = pool)]
+    pub vault_in: Account<'info, TokenAccount>,
+    ///  BUG: Either real vault, whichever the caller wants drained
+    #[account(mut, token::authority = pool)]
+    pub vault_out: Account<'info, TokenAccount>,
+    #[account(mut, token::authority = user)]
+    pub user_in: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_out: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Both mints must have the same decimals")]
+    DecimalsMismatch,
+}
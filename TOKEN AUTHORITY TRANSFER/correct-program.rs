@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, SetAuthority, Token, TokenAccount, Transfer as SplTransfer};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+
+declare_id!("TAuth5ecur222222222222222222222222222222222");
+
+#[program]
+pub mod token_authority_transfer_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, successor_program: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = ctx.accounts.treasury.key();
+        config.successor_program = successor_program;
+        config.bump = ctx.bumps.config;
+        config.authority_bump = ctx.bumps.treasury_authority;
+        msg!("Treasury initialized");
+        Ok(())
+    }
+
+    /// Pays out of the treasury, signed by the treasury authority PDA
+    pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+        let seeds = &[b"treasury_authority".as_ref(), &[ctx.accounts.config.authority_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+        msg!("Paid {} tokens from treasury", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Treasury can only move to the successor program's PDA
+    /// The destination is derived on-chain, never taken from the caller
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+        //  Derive the only acceptable new authority
+        let (expected_authority, _) = Pubkey::find_program_address(
+            &[b"treasury_authority"],
+            &ctx.accounts.config.successor_program,
+        );
+        require_keys_eq!(
+            ctx.accounts.new_authority.key(),
+            expected_authority,
+            ErrorCode::InvalidNewAuthority
+        );
+
+        let seeds = &[b"treasury_authority".as_ref(), &[ctx.accounts.config.authority_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.treasury_authority.to_account_info(),
+                account_or_mint: ctx.accounts.treasury.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::set_authority(cpi_ctx, AuthorityType::AccountOwner, Some(expected_authority))?;
+
+        msg!(" Treasury authority handed to successor PDA {}", expected_authority);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: PDA used only as the treasury's token authority
+    #[account(seeds = [b"treasury_authority"], bump)]
+    pub treasury_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, has_one = treasury)]
+    pub config: Account<'info, Config>,
+    /// CHECK: PDA signer for the treasury
+    #[account(seeds = [b"treasury_authority"], bump = config.authority_bump)]
+    pub treasury_authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, has_one = treasury)]
+    pub config: Account<'info, Config>,
+    /// CHECK: PDA signer for the treasury
+    #[account(seeds = [b"treasury_authority"], bump = config.authority_bump)]
+    pub treasury_authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+    /// CHECK: Must equal the successor program's treasury_authority PDA
+    pub new_authority: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    ///  Only program allowed to take over the treasury
+    pub successor_program: Pubkey,
+    pub bump: u8,
+    pub authority_bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("New authority is not the successor program's treasury PDA")]
+    InvalidNewAuthority,
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, SetAuthority, Token, TokenAccount, Transfer as SplTransfer};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+
+declare_id!("TAuthvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod token_authority_transfer_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = ctx.accounts.treasury.key();
+        config.bump = ctx.bumps.config;
+        config.authority_bump = ctx.bumps.treasury_authority;
+        msg!("Treasury initialized");
+        Ok(())
+    }
+
+    /// Pays out of the treasury, signed by the treasury authority PDA
+    pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+        let seeds = &[b"treasury_authority".as_ref(), &[ctx.accounts.config.authority_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SplTransfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+        msg!("Paid {} tokens from treasury", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Hands the treasury to ANY pubkey the caller supplies!
+    /// One bad argument (typo, phished admin, buggy client) loses the treasury
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>, new_authority: Pubkey) -> Result<()> {
+        let seeds = &[b"treasury_authority".as_ref(), &[ctx.accounts.config.authority_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        //  new_authority is never validated
+        // It could be an attacker's wallet - the program can never sign again
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.treasury_authority.to_account_info(),
+                account_or_mint: ctx.accounts.treasury.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::set_authority(cpi_ctx, AuthorityType::AccountOwner, Some(new_authority))?;
+
+        msg!(" Treasury authority handed to unvalidated key {}", new_authority);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: PDA used only as the treasury's token authority
+    #[account(seeds = [b"treasury_authority"], bump)]
+    pub treasury_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, has_one = treasury)]
+    pub config: Account<'info, Config>,
+    /// CHECK: PDA signer for the treasury
+    #[account(seeds = [b"treasury_authority"], bump = config.authority_bump)]
+    pub treasury_authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, has_one = treasury)]
+    pub config: Account<'info, Config>,
+    /// CHECK: PDA signer for the treasury
+    #[account(seeds = [b"treasury_authority"], bump = config.authority_bump)]
+    pub treasury_authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub bump: u8,
+    pub authority_bump: u8,
+}
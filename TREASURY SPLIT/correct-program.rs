@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+
+// SHARED UTILS/safe_cast.rs, used by percentage.rs
+mod safe_cast;
+
+// SHARED UTILS/percentage.rs
+mod percentage;
+use percentage::BPS_DENOMINATOR;
+
+declare_id!("Treasury5ecur222222222222222222222222222222");
+
+/// Number of payees in a split
+pub const RECIPIENTS: usize = 3;
+
+#[program]
+pub mod treasury_split_secure {
+    use super::*;
+
+    pub fn configure(
+        ctx: Context<Configure>,
+        recipients: [Pubkey; RECIPIENTS],
+        bps: [u16; RECIPIENTS],
+    ) -> Result<()> {
+        //  FIX: Shares must cover exactly 100%, no more and no less
+        let total: u64 = bps.iter().map(|b| *b as u64).sum();
+        require!(total == BPS_DENOMINATOR, ErrorCode::BpsSumInvalid);
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.recipients = recipients;
+        config.bps = bps;
+        config.undistributed = 0;
+        config.bump = ctx.bumps.config;
+        msg!("Split configured: {:?} bps", bps);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.config.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let config = &mut ctx.accounts.config;
+        config.undistributed = config.undistributed.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Shares always add up to exactly `amount`
+    pub fn distribute(ctx: Context<Distribute>, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(amount <= config.undistributed, ErrorCode::InsufficientFunds);
+
+        let shares = split(amount, &config.bps)?;
+
+        let recipients = [
+            ctx.accounts.recipient_0.to_account_info(),
+            ctx.accounts.recipient_1.to_account_info(),
+            ctx.accounts.recipient_2.to_account_info(),
+        ];
+        for (recipient, share) in recipients.iter().zip(shares) {
+            ctx.accounts.config.sub_lamports(share)?;
+            recipient.add_lamports(share)?;
+        }
+
+        let config = &mut ctx.accounts.config;
+        config.undistributed = config
+            .undistributed
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        msg!(" Distributed {} lamports as {:?}", amount, shares);
+        Ok(())
+    }
+}
+
+/// Splits `amount` by `bps` so the shares sum to `amount` exactly
+///
+/// Every share but the last is floored; the last recipient takes whatever
+/// is left, so rounding dust always lands in the same place.
+pub fn split(amount: u64, bps: &[u16; RECIPIENTS]) -> Result<[u64; RECIPIENTS]> {
+    let mut shares = [0u64; RECIPIENTS];
+    let mut assigned: u64 = 0;
+    for (share, bps) in shares[..RECIPIENTS - 1].iter_mut().zip(bps) {
+        //  FIX: Widened to u128 inside, so `amount * bps` can't overflow
+        *share = percentage::percentage_of(amount, *bps)?;
+        assigned = assigned.checked_add(*share).ok_or(ErrorCode::Overflow)?;
+    }
+    //  FIX: The remainder, not a floored share, goes to the last recipient
+    shares[RECIPIENTS - 1] = amount.checked_sub(assigned).ok_or(ErrorCode::Overflow)?;
+    Ok(shares)
+}
+
+#[derive(Accounts)]
+pub struct Configure<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + SplitConfig::INIT_SPACE,
+        seeds = [b"split_config", admin.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, SplitConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"split_config", config.admin.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SplitConfig>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: recipients are pinned to the configured addresses
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(
+        mut,
+        seeds = [b"split_config", config.admin.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SplitConfig>,
+    /// CHECK: Must be the configured recipient
+    #[account(mut, address = config.recipients[0])]
+    pub recipient_0: AccountInfo<'info>,
+    /// CHECK: Must be the configured recipient
+    #[account(mut, address = config.recipients[1])]
+    pub recipient_1: AccountInfo<'info>,
+    /// CHECK: Must be the configured recipient
+    #[account(mut, address = config.recipients[2])]
+    pub recipient_2: AccountInfo<'info>,
+}
+
+/// Holds the undistributed lamports and how to split them
+#[account]
+#[derive(InitSpace)]
+pub struct SplitConfig {
+    pub admin: Pubkey,
+    pub recipients: [Pubkey; RECIPIENTS],
+    pub bps: [u16; RECIPIENTS],
+    pub undistributed: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient undistributed funds")]
+    InsufficientFunds,
+    #[msg("Split shares must sum to 10000 bps")]
+    BpsSumInvalid,
+}
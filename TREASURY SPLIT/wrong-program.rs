@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Treasuryvu1n1111111111111111111111111111111");
+
+/// Number of payees in a split
+pub const RECIPIENTS: usize = 3;
+
+/// 100% in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[program]
+pub mod treasury_split_vulnerable {
+    use super::*;
+
+    ///  VULNERABLE: Accepts shares that don't add up to 100%!
+    pub fn configure(
+        ctx: Context<Configure>,
+        recipients: [Pubkey; RECIPIENTS],
+        bps: [u16; RECIPIENTS],
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.recipients = recipients;
+        config.bps = bps;
+        config.undistributed = 0;
+        config.bump = ctx.bumps.config;
+        msg!("Split configured: {:?} bps", bps);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.config.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let config = &mut ctx.accounts.config;
+        config.undistributed = config.undistributed.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Each share is floored on its own, and nothing checks the total!
+    pub fn distribute(ctx: Context<Distribute>, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(amount <= config.undistributed, ErrorCode::InsufficientFunds);
+
+        let mut shares = [0u64; RECIPIENTS];
+        for (share, bps) in shares.iter_mut().zip(config.bps) {
+            //  BUG: Rounds down every time, and `amount * bps` can overflow
+            *share = amount * bps as u64 / BPS_DENOMINATOR;
+        }
+
+        let recipients = [
+            ctx.accounts.recipient_0.to_account_info(),
+            ctx.accounts.recipient_1.to_account_info(),
+            ctx.accounts.recipient_2.to_account_info(),
+        ];
+        for (recipient, share) in recipients.iter().zip(shares) {
+            ctx.accounts.config.sub_lamports(share)?;
+            recipient.add_lamports(share)?;
+        }
+
+        //  The ledger drops `amount`, whatever was actually paid
+        ctx.accounts.config.undistributed -= amount;
+        msg!(" Distributed {} lamports as {:?}", amount, shares);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Configure<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + SplitConfig::INIT_SPACE,
+        seeds = [b"split_config", admin.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, SplitConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"split_config", config.admin.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SplitConfig>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: recipients are pinned to the configured addresses
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(
+        mut,
+        seeds = [b"split_config", config.admin.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SplitConfig>,
+    /// CHECK: Must be the configured recipient
+    #[account(mut, address = config.recipients[0])]
+    pub recipient_0: AccountInfo<'info>,
+    /// CHECK: Must be the configured recipient
+    #[account(mut, address = config.recipients[1])]
+    pub recipient_1: AccountInfo<'info>,
+    /// CHECK: Must be the configured recipient
+    #[account(mut, address = config.recipients[2])]
+    pub recipient_2: AccountInfo<'info>,
+}
+
+/// Holds the undistributed lamports and how to split them
+#[account]
+#[derive(InitSpace)]
+pub struct SplitConfig {
+    pub admin: Pubkey,
+    pub recipients: [Pubkey; RECIPIENTS],
+    pub bps: [u16; RECIPIENTS],
+    pub undistributed: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient undistributed funds")]
+    InsufficientFunds,
+}
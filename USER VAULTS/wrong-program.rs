@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+declare_id!("UVau1tvu1n111111111111111111111111111111111");
+
+/// A label is used as a seed, and a seed is at most 32 bytes
+pub const MAX_LABEL_LEN: usize = 32;
+
+#[program]
+pub mod user_vaults_vulnerable {
+    use super::*;
+
+    ///  VULNERABLE: The vault's address comes from the label alone.
+    /// Whoever opens "savings" first owns "savings" for everyone
+    pub fn open_vault(ctx: Context<OpenVault>, label: String) -> Result<()> {
+        require!(label.len() <= MAX_LABEL_LEN, ErrorCode::LabelTooLong);
+
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.label = label;
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!(" Opened vault \"{}\" for {}", vault.label, vault.owner);
+        Ok(())
+    }
+
+    /// Anyone can pay into a vault by its label - a payroll run, a friend
+    pub fn deposit(ctx: Context<Deposit>, label: String, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!(" Deposited {} lamports into \"{}\"", amount, label);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, _label: String, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault
+            .balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(label: String)]
+pub struct OpenVault<'info> {
+    //  BUG: Nothing user-specific in the seeds - one "savings" in the whole program
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", label.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(label: String)]
+pub struct Deposit<'info> {
+    //  BUG: The label finds *a* vault, not the one the depositor meant
+    #[account(mut, seeds = [b"vault", label.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(label: String)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", label.as_bytes()],
+        bump = vault.bump,
+        has_one = owner,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub owner: Pubkey,
+    #[max_len(MAX_LABEL_LEN)]
+    pub label: String,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Label is longer than 32 bytes")]
+    LabelTooLong,
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Feevu1n111111111111111111111111111111111111");
+
+#[program]
+pub mod withdraw_fee_vulnerable {
+    use super::*;
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_lamports: u64) -> Result<()> {
+        let fee_config = &mut ctx.accounts.fee_config;
+        fee_config.admin = ctx.accounts.admin.key();
+        fee_config.fee_lamports = fee_lamports;
+        fee_config.bump = ctx.bumps.fee_config;
+
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        fee_vault.total_collected = 0;
+        fee_vault.bump = ctx.bumps.fee_vault;
+        msg!("Fee config initialized: {} lamports per withdrawal", fee_lamports);
+        Ok(())
+    }
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Vault initialized for authority: {}", vault.authority);
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance + amount;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Fee is subtracted with raw arithmetic!
+    /// Withdrawing less than the fee underflows the payout
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let fee = ctx.accounts.fee_config.fee_lamports;
+
+        //  If amount = 1_000 and fee = 5_000
+        // Result: net = 18446744073709547616 (underflow!)
+        let net = amount - fee;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance - amount;
+
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        fee_vault.total_collected = fee_vault.total_collected + fee;
+
+        //  Vault pays out fee + net, which is NOT amount once net wrapped
+        **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.fee_vault.to_account_info().try_borrow_mut_lamports()? += fee;
+        **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += net;
+
+        msg!(" Withdrew {} lamports, paid out {} after fee", amount, net);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeConfig::INIT_SPACE,
+        seeds = [b"fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"fee_config"], bump = fee_config.bump)]
+    pub fee_config: Account<'info, FeeConfig>,
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeConfig {
+    pub admin: Pubkey,
+    pub fee_lamports: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub total_collected: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Quorum5ecur22222222222222222222222222222222");
+
+pub const MAX_SIGNERS: usize = 5;
+
+#[program]
+pub mod withdraw_quorum_secure {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(signers.len() <= MAX_SIGNERS, ErrorCode::TooManySigners);
+        //  FIX: A threshold of 0 or above the member count is unusable
+        require!(
+            threshold >= 1 && threshold as usize <= signers.len(),
+            ErrorCode::InvalidThreshold
+        );
+        //  FIX: Duplicate members would let one key count twice
+        for (i, key) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(key), ErrorCode::DuplicateSigner);
+        }
+
+        let vault = &mut ctx.accounts.vault;
+        vault.creator = ctx.accounts.creator.key();
+        vault.signers = signers;
+        vault.threshold = threshold;
+        vault.balance = 0;
+        vault.bump = ctx.bumps.vault;
+        msg!("Shared vault created, {} of {} required", threshold, vault.signers.len());
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    ///  SECURE: Requires `threshold` distinct member signatures
+    /// Co-signers are passed in remaining_accounts
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+
+        //  FIX: Count distinct members who actually signed
+        let mut approved: Vec<Pubkey> = Vec::with_capacity(MAX_SIGNERS);
+        for account in ctx.remaining_accounts.iter() {
+            require!(account.is_signer, ErrorCode::MissingSignature);
+            require!(vault.signers.contains(account.key), ErrorCode::NotASigner);
+            //  FIX: The same key passed twice only counts once
+            require!(!approved.contains(account.key), ErrorCode::DuplicateSigner);
+            approved.push(*account.key);
+        }
+        require!(
+            approved.len() >= vault.threshold as usize,
+            ErrorCode::QuorumNotMet
+        );
+
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+
+        msg!(" Withdrew {} lamports with {} of {} approvals", amount, approved.len(), vault.threshold);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", creator.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+///  SECURE: No single Signer field; approvals come from remaining_accounts
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: Destination for the withdrawn lamports
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub creator: Pubkey,
+    #[max_len(MAX_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Too many signers")]
+    TooManySigners,
+    #[msg("Caller is not a vault signer")]
+    NotASigner,
+    #[msg("Threshold must be between 1 and the number of signers")]
+    InvalidThreshold,
+    #[msg("Signer listed more than once")]
+    DuplicateSigner,
+    #[msg("Co-signer account did not sign")]
+    MissingSignature,
+    #[msg("Not enough approvals to withdraw")]
+    QuorumNotMet,
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+
+declare_id!("WLockvu1n1111111111111111111111111111111111");
+
+#[program]
+pub mod write_lock_vulnerable {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        ctx.accounts.pool.bump = ctx.bumps.pool;
+        msg!("Pool initialized");
+        Ok(())
+    }
+
+    pub fn open_account(ctx: Context<OpenAccount>) -> Result<()> {
+        let user = &mut ctx.accounts.user_account;
+        user.owner = ctx.accounts.owner.key();
+        user.balance = 0;
+        user.bump = ctx.bumps.user_account;
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let user = &mut ctx.accounts.user_account;
+        user.balance = user.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Read, check and write in one instruction. Two of these racing for the
+    /// same `user_account` cannot both pass: the runtime write-locks it, so
+    /// the second transaction runs after the first and reads its result
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let user = &mut ctx.accounts.user_account;
+        user.balance = user.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        ctx.accounts.pool.sub_lamports(amount)?;
+        ctx.accounts.owner.add_lamports(amount)?;
+        msg!(" Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    /// Step 1 of a delayed withdrawal: check the balance and record the request
+    pub fn request_withdraw(ctx: Context<RequestWithdraw>, request_id: u64, amount: u64) -> Result<()> {
+        let balance = ctx.accounts.user_account.balance;
+        require!(amount <= balance, ErrorCode::InsufficientFunds);
+
+        let request = &mut ctx.accounts.request;
+        request.owner = ctx.accounts.owner.key();
+        request.request_id = request_id;
+        request.amount = amount;
+        //  BUG: Derived from a read that will be stale by the time it's used
+        request.balance_after = balance - amount;
+        request.bump = ctx.bumps.request;
+        msg!("Withdrawal {} requested for {} lamports", request_id, amount);
+        Ok(())
+    }
+
+    ///  VULNERABLE: Step 2 trusts state computed in an earlier instruction!
+    pub fn execute_withdraw(ctx: Context<ExecuteWithdraw>) -> Result<()> {
+        let request = &ctx.accounts.request;
+
+        //  Another request may have paid out since this one was checked
+        ctx.accounts.user_account.balance = request.balance_after;
+        ctx.accounts.pool.sub_lamports(request.amount)?;
+        ctx.accounts.owner.add_lamports(request.amount)?;
+
+        msg!(" Executed withdrawal {} for {} lamports", request.request_id, request.amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenAccount<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + UserAccount::INIT_SPACE,
+        seeds = [b"user", owner.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump = user_account.bump,
+        has_one = owner,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump = user_account.bump,
+        has_one = owner,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct RequestWithdraw<'info> {
+    #[account(
+        seeds = [b"user", owner.key().as_ref()],
+        bump = user_account.bump,
+        has_one = owner,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + WithdrawRequest::INIT_SPACE,
+        seeds = [b"request", owner.key().as_ref(), &request_id.to_le_bytes()],
+        bump
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdraw<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"user", owner.key().as_ref()],
+        bump = user_account.bump,
+        has_one = owner,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"request", owner.key().as_ref(), &request.request_id.to_le_bytes()],
+        bump = request.bump,
+        has_one = owner,
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Holds every user's deposits
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserAccount {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawRequest {
+    pub owner: Pubkey,
+    pub request_id: u64,
+    pub amount: u64,
+    pub balance_after: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+}